@@ -0,0 +1,161 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{AttributeArgs, Fields, ItemEnum};
+
+use crate::utils::{c_string_lit, parse_string_args, to_nick};
+
+fn rename(ident: &str, rename_all: Option<&str>) -> Result<String, String> {
+    let nick = to_nick(ident);
+
+    match rename_all {
+        None => Ok(ident.to_string()),
+        Some("kebab-case") => Ok(nick),
+        Some("snake_case") => Ok(nick.replace('-', "_")),
+        Some("SCREAMING-KEBAB-CASE") => Ok(nick.to_uppercase()),
+        Some("SCREAMING_SNAKE_CASE") => Ok(nick.replace('-', "_").to_uppercase()),
+        Some(other) => Err(format!("unsupported `rename_all` value `{}`", other)),
+    }
+}
+
+pub fn impl_glib_enum(args: &AttributeArgs, input: &ItemEnum) -> syn::Result<TokenStream> {
+    let mut args = parse_string_args(args, &["name", "rename_all"])?;
+    let rename_all = args.pop().unwrap();
+    let name = match args.pop().unwrap() {
+        Some(name) => name,
+        None => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "missing `name = \"...\"` argument",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let type_name = c_string_lit(&name, ident.span())?;
+    let n_values = input.variants.len() + 1;
+
+    let mut values = Vec::new();
+    let mut from_glib = Vec::new();
+    let mut to_glib = Vec::new();
+    let mut to_nick_arms = Vec::new();
+    let mut from_nick_arms = Vec::new();
+    for variant in &input.variants {
+        match variant.fields {
+            Fields::Unit => (),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "only variants without fields are supported",
+                ))
+            }
+        }
+
+        let variant_ident = &variant.ident;
+        let value_name = rename(&variant_ident.to_string(), rename_all.as_ref().map(|s| &**s))
+            .map_err(|err| syn::Error::new(Span::call_site(), err))?;
+        let value_name = c_string_lit(&value_name, variant_ident.span())?;
+        let nick = to_nick(&variant_ident.to_string());
+        let value_nick = c_string_lit(&nick, variant_ident.span())?;
+
+        values.push(quote! {
+            ::glib::gobject_sys::GEnumValue {
+                value: #ident::#variant_ident as i32,
+                value_name: #value_name as *const _ as *const _,
+                value_nick: #value_nick as *const _ as *const _,
+            },
+        });
+        from_glib.push(quote! {
+            value if value == #ident::#variant_ident as i32 => Some(#ident::#variant_ident),
+        });
+        to_glib.push(quote! {
+            #ident::#variant_ident => #ident::#variant_ident as i32,
+        });
+        to_nick_arms.push(quote! {
+            #ident::#variant_ident => #nick,
+        });
+        from_nick_arms.push(quote! {
+            #nick => Ok(#ident::#variant_ident),
+        });
+    }
+
+    Ok(quote! {
+        #input
+
+        impl ::glib::StaticType for #ident {
+            fn static_type() -> ::glib::Type {
+                static ONCE: ::std::sync::Once = ::std::sync::Once::new();
+                static mut TYPE: ::glib::Type = ::glib::Type::Invalid;
+
+                ONCE.call_once(|| unsafe {
+                    // GLib keeps pointers to the values for the whole lifetime of the type
+                    let values: Box<[::glib::gobject_sys::GEnumValue; #n_values]> = Box::new([
+                        #(#values)*
+                        ::glib::gobject_sys::GEnumValue {
+                            value: 0,
+                            value_name: ::std::ptr::null(),
+                            value_nick: ::std::ptr::null(),
+                        },
+                    ]);
+
+                    let type_ = ::glib::gobject_sys::g_enum_register_static(
+                        #type_name as *const _ as *const _,
+                        Box::leak(values).as_ptr(),
+                    );
+                    assert_ne!(type_, ::glib::gobject_sys::G_TYPE_INVALID);
+                    TYPE = ::glib::translate::from_glib(type_);
+                });
+
+                unsafe { TYPE }
+            }
+        }
+
+        // C code can store any integer in the value. Unknown ones can't be represented by the
+        // enum, so these are returned as `None` instead of implementing `FromValue`.
+        impl<'a> ::glib::value::FromValueOptional<'a> for #ident {
+            unsafe fn from_value_optional(value: &::glib::Value) -> Option<Self> {
+                match ::glib::gobject_sys::g_value_get_enum(
+                    ::glib::translate::ToGlibPtr::to_glib_none(value).0,
+                ) {
+                    #(#from_glib)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl ::glib::value::SetValue for #ident {
+            unsafe fn set_value(value: &mut ::glib::Value, this: &Self) {
+                let v = match *this {
+                    #(#to_glib)*
+                };
+                ::glib::gobject_sys::g_value_set_enum(
+                    ::glib::translate::ToGlibPtrMut::to_glib_none_mut(value).0,
+                    v,
+                )
+            }
+        }
+
+        impl ::std::fmt::Display for #ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                let nick = match *self {
+                    #(#to_nick_arms)*
+                };
+                f.write_str(nick)
+            }
+        }
+
+        impl ::std::str::FromStr for #ident {
+            type Err = ::glib::BoolError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    #(#from_nick_arms)*
+                    _ => Err(::glib::glib_bool_error!("Invalid {} nick `{}`", #name, s)),
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,149 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, Attribute, AttributeArgs, Expr, Ident, ItemMacro, Token, Type, Visibility};
+
+use crate::utils::{c_string_lit, parse_string_args, to_nick};
+
+/// The contents of a `bitflags! { ... }` invocation with a single struct.
+struct BitflagsInput {
+    ident: Ident,
+    ty: Type,
+    flags: Vec<Ident>,
+}
+
+impl Parse for BitflagsInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Attribute::parse_outer(input)?;
+        input.parse::<Visibility>()?;
+        input.parse::<Token![struct]>()?;
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+
+        let content;
+        braced!(content in input);
+        let mut flags = Vec::new();
+        while !content.is_empty() {
+            Attribute::parse_outer(&content)?;
+            content.parse::<Token![const]>()?;
+            flags.push(content.parse()?);
+            content.parse::<Token![=]>()?;
+            content.parse::<Expr>()?;
+            content.parse::<Token![;]>()?;
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("only a single flags struct is supported"));
+        }
+
+        Ok(BitflagsInput { ident, ty, flags })
+    }
+}
+
+pub fn impl_glib_flags(args: &AttributeArgs, input: &ItemMacro) -> syn::Result<TokenStream> {
+    let is_bitflags = input
+        .mac
+        .path
+        .segments
+        .last()
+        .map_or(false, |segment| segment.ident == "bitflags");
+    if !is_bitflags {
+        return Err(syn::Error::new_spanned(
+            &input.mac.path,
+            "expected a `bitflags!` invocation",
+        ));
+    }
+
+    let name = match parse_string_args(args, &["name"])?.remove(0) {
+        Some(name) => name,
+        None => {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "missing `name = \"...\"` argument",
+            ))
+        }
+    };
+
+    let flags: BitflagsInput = syn::parse2(input.mac.tokens.clone())?;
+    let ident = &flags.ident;
+    let ty = &flags.ty;
+    let type_name = c_string_lit(&name, ident.span())?;
+    let n_values = flags.flags.len() + 1;
+
+    let values = flags
+        .flags
+        .iter()
+        .map(|flag| {
+            let value_name = c_string_lit(&flag.to_string(), flag.span())?;
+            let value_nick = c_string_lit(&to_nick(&flag.to_string()), flag.span())?;
+            Ok(quote! {
+                ::glib::gobject_sys::GFlagsValue {
+                    value: #ident::#flag.bits() as u32,
+                    value_name: #value_name as *const _ as *const _,
+                    value_nick: #value_nick as *const _ as *const _,
+                },
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #input
+
+        impl ::glib::StaticType for #ident {
+            fn static_type() -> ::glib::Type {
+                static ONCE: ::std::sync::Once = ::std::sync::Once::new();
+                static mut TYPE: ::glib::Type = ::glib::Type::Invalid;
+
+                ONCE.call_once(|| unsafe {
+                    // GLib keeps pointers to the values for the whole lifetime of the type
+                    let values: Box<[::glib::gobject_sys::GFlagsValue; #n_values]> = Box::new([
+                        #(#values)*
+                        ::glib::gobject_sys::GFlagsValue {
+                            value: 0,
+                            value_name: ::std::ptr::null(),
+                            value_nick: ::std::ptr::null(),
+                        },
+                    ]);
+
+                    let type_ = ::glib::gobject_sys::g_flags_register_static(
+                        #type_name as *const _ as *const _,
+                        Box::leak(values).as_ptr(),
+                    );
+                    assert_ne!(type_, ::glib::gobject_sys::G_TYPE_INVALID);
+                    TYPE = ::glib::translate::from_glib(type_);
+                });
+
+                unsafe { TYPE }
+            }
+        }
+
+        impl<'a> ::glib::value::FromValueOptional<'a> for #ident {
+            unsafe fn from_value_optional(value: &::glib::Value) -> Option<Self> {
+                Some(::glib::value::FromValue::from_value(value))
+            }
+        }
+
+        impl<'a> ::glib::value::FromValue<'a> for #ident {
+            unsafe fn from_value(value: &::glib::Value) -> Self {
+                let bits = ::glib::gobject_sys::g_value_get_flags(
+                    ::glib::translate::ToGlibPtr::to_glib_none(value).0,
+                );
+                #ident::from_bits_truncate(bits as #ty)
+            }
+        }
+
+        impl ::glib::value::SetValue for #ident {
+            unsafe fn set_value(value: &mut ::glib::Value, this: &Self) {
+                ::glib::gobject_sys::g_value_set_flags(
+                    ::glib::translate::ToGlibPtrMut::to_glib_none_mut(value).0,
+                    this.bits() as u32,
+                )
+            }
+        }
+    })
+}
@@ -0,0 +1,184 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Procedural macros for the Rust GLib bindings.
+
+extern crate proc_macro;
+
+mod enums;
+mod flags;
+mod utils;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, AttributeArgs, Expr, ImplItem, ItemEnum, ItemImpl, ItemMacro,
+    Lit,
+};
+
+/// Attribute macro for boilerplate of `ObjectSubclass` implementations.
+///
+/// This is the attribute equivalent of the `glib_object_subclass!` macro and
+/// generates the `type_data()` and `get_type()` functions of the annotated
+/// `impl ObjectSubclass for ...` block.
+///
+/// If `NAME` is given as a string literal it is also checked at compile time
+/// to not contain any interior NUL bytes.
+///
+/// ```ignore
+/// use glib_macros::glib_object_subclass;
+///
+/// #[glib_object_subclass]
+/// impl ObjectSubclass for SimpleObject {
+///     const NAME: &'static str = "SimpleObject";
+///     type ParentType = glib::Object;
+///     type Instance = subclass::simple::InstanceStruct<Self>;
+///     type Class = subclass::simple::ClassStruct<Self>;
+///
+///     fn new() -> Self {
+///         Self
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn glib_object_subclass(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(item as ItemImpl);
+
+    if let Some(err) = check_name(&input) {
+        return err.to_compile_error().into();
+    }
+
+    let type_data: ImplItem = parse_quote! {
+        fn type_data() -> ::std::ptr::NonNull<::glib::subclass::TypeData> {
+            static mut DATA: ::glib::subclass::TypeData = ::glib::subclass::TypeData {
+                type_: ::glib::Type::Invalid,
+                parent_class: ::std::ptr::null_mut(),
+                interface_data: ::std::ptr::null_mut(),
+                private_offset: 0,
+                class_private: None,
+            };
+
+            unsafe { ::std::ptr::NonNull::new_unchecked(&mut DATA) }
+        }
+    };
+
+    let get_type: ImplItem = parse_quote! {
+        fn get_type() -> ::glib::Type {
+            static ONCE: ::std::sync::Once = ::std::sync::Once::new();
+
+            ONCE.call_once(|| {
+                ::glib::subclass::register_type::<Self>();
+            });
+
+            unsafe {
+                let data = Self::type_data();
+                let type_ = data.as_ref().get_type();
+                assert_ne!(type_, ::glib::Type::Invalid);
+
+                type_
+            }
+        }
+    };
+
+    input.items.push(type_data);
+    input.items.push(get_type);
+
+    let output = quote! { #input };
+    output.into()
+}
+
+/// Attribute macro for registering a `bitflags!` struct as a `GFlags` type.
+///
+/// This generates `StaticType`, `FromValue` and `SetValue` implementations so
+/// the flags can be stored in a `glib::Value` and used for properties. The
+/// `GType` is registered with the given `name` on first use, each flag uses
+/// its identifier as value name and the lowercase, dash-separated identifier
+/// as nick.
+///
+/// A property of the flags type can be created with the existing
+/// `ParamSpec::flags` constructor by passing `MyFlags::static_type()`.
+///
+/// ```ignore
+/// use glib_macros::glib_flags;
+///
+/// #[glib_flags(name = "MyFlags")]
+/// bitflags::bitflags! {
+///     struct MyFlags: u32 {
+///         const FOO = 1;
+///         const BAR_BAZ = 2;
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn glib_flags(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as AttributeArgs);
+    let input = parse_macro_input!(item as ItemMacro);
+
+    match flags::impl_glib_flags(&args, &input) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Attribute macro for registering a Rust enum as a `GEnum` type.
+///
+/// This generates `StaticType`, `FromValueOptional` and `SetValue` implementations so
+/// the enum can be stored in a `glib::Value` and used for properties, and
+/// `Display` / `FromStr` implementations based on the enum value nicks.
+///
+/// Values that don't correspond to any variant, e.g. set from C code, are
+/// returned as `None` by `Value::get()`.
+///
+/// The `GType` is registered with the given `name` on first use. Each variant
+/// uses its identifier as value name, which can be changed with `rename_all`
+/// to one of `snake_case`, `kebab-case`, `SCREAMING_SNAKE_CASE` or
+/// `SCREAMING-KEBAB-CASE`. The nick is always the lowercase, dash-separated
+/// identifier. Only variants without fields are supported.
+///
+/// A property of the enum type can be created with the existing
+/// `ParamSpec::enum_` constructor by passing `MyEnum::static_type()`.
+///
+/// ```ignore
+/// use glib_macros::glib_enum;
+///
+/// #[glib_enum(name = "MyEnum", rename_all = "SCREAMING_SNAKE_CASE")]
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// enum MyEnum {
+///     Foo,
+///     BarBaz = 5,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn glib_enum(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as AttributeArgs);
+    let input = parse_macro_input!(item as ItemEnum);
+
+    match enums::impl_glib_enum(&args, &input) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn check_name(input: &ItemImpl) -> Option<syn::Error> {
+    for item in &input.items {
+        if let ImplItem::Const(ref item) = *item {
+            if item.ident != "NAME" {
+                continue;
+            }
+
+            if let Expr::Lit(ref expr) = item.expr {
+                if let Lit::Str(ref name) = expr.lit {
+                    if name.value().contains('\0') {
+                        return Some(syn::Error::new_spanned(
+                            name,
+                            "`NAME` must not contain NUL bytes",
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
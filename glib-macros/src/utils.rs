@@ -0,0 +1,70 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use proc_macro2::Span;
+use syn::{AttributeArgs, Lit, Meta, NestedMeta};
+
+/// Looks up the string value of `key = "value"` in the attribute arguments.
+///
+/// Unknown keys are reported as errors.
+pub fn parse_string_args(
+    args: &AttributeArgs,
+    keys: &[&str],
+) -> Result<Vec<Option<String>>, syn::Error> {
+    let mut values = vec![None; keys.len()];
+
+    for arg in args {
+        let nv = match *arg {
+            NestedMeta::Meta(Meta::NameValue(ref nv)) => nv,
+            _ => return Err(syn::Error::new_spanned(arg, "expected `key = \"value\"`")),
+        };
+
+        let index = match keys.iter().position(|key| nv.path.is_ident(key)) {
+            Some(index) => index,
+            None => return Err(syn::Error::new_spanned(&nv.path, "unknown argument")),
+        };
+
+        match nv.lit {
+            Lit::Str(ref s) => values[index] = Some(s.value()),
+            _ => return Err(syn::Error::new_spanned(&nv.lit, "expected a string literal")),
+        }
+    }
+
+    Ok(values)
+}
+
+/// Returns the string as a NUL-terminated byte string literal.
+pub fn c_string_lit(s: &str, span: Span) -> Result<syn::LitByteStr, syn::Error> {
+    if s.contains('\0') {
+        return Err(syn::Error::new(span, "string must not contain NUL bytes"));
+    }
+
+    let mut bytes = s.as_bytes().to_vec();
+    bytes.push(0);
+    Ok(syn::LitByteStr::new(&bytes, span))
+}
+
+/// Converts a Rust identifier like `FooBar` or `FOO_BAR` to a GLib nick like `foo-bar`.
+pub fn to_nick(ident: &str) -> String {
+    let mut nick = String::with_capacity(ident.len());
+    let mut prev_lower = false;
+
+    for c in ident.chars() {
+        if c == '_' {
+            nick.push('-');
+            prev_lower = false;
+        } else if c.is_uppercase() {
+            if prev_lower {
+                nick.push('-');
+            }
+            nick.extend(c.to_lowercase());
+            prev_lower = false;
+        } else {
+            nick.push(c);
+            prev_lower = true;
+        }
+    }
+
+    nick
+}
@@ -0,0 +1,91 @@
+use glib::prelude::*;
+use glib::subclass;
+use glib::subclass::prelude::*;
+use glib::translate::ToGlibPtrMut;
+use glib_macros::{glib_enum, glib_flags, glib_object_subclass};
+
+pub struct SimpleObject;
+
+#[glib_object_subclass]
+impl ObjectSubclass for SimpleObject {
+    const NAME: &'static str = "MacroSimpleObject";
+    type ParentType = glib::Object;
+    type Instance = subclass::simple::InstanceStruct<Self>;
+    type Class = subclass::simple::ClassStruct<Self>;
+
+    fn new() -> Self {
+        SimpleObject
+    }
+}
+
+impl ObjectImpl for SimpleObject {
+    glib::glib_object_impl!();
+}
+
+#[test]
+fn test_object_subclass() {
+    let type_ = SimpleObject::get_type();
+    assert_eq!(type_.name(), "MacroSimpleObject");
+
+    let obj = glib::Object::new(type_, &[]).unwrap();
+    assert!(obj.get_type().is_a(&type_));
+}
+
+#[glib_flags(name = "MacroTestFlags")]
+bitflags::bitflags! {
+    struct TestFlags: u32 {
+        const FOO = 1;
+        const BAR_BAZ = 2;
+    }
+}
+
+#[test]
+fn test_flags() {
+    let type_ = TestFlags::static_type();
+    assert_eq!(type_.name(), "MacroTestFlags");
+    assert!(type_.is_a(&glib::Type::BaseFlags));
+
+    let class = glib::FlagsClass::new(type_).unwrap();
+    let value = class.get_value_by_nick("bar-baz").unwrap();
+    assert_eq!(value.get_value(), 2);
+    assert_eq!(value.get_name(), "BAR_BAZ");
+
+    let flags = TestFlags::FOO | TestFlags::BAR_BAZ;
+    let value = flags.to_value();
+    assert_eq!(value.type_(), type_);
+    assert_eq!(value.get_some::<TestFlags>(), Ok(flags));
+    assert_eq!(value.get::<TestFlags>(), Ok(Some(flags)));
+}
+
+#[glib_enum(name = "MacroTestEnum", rename_all = "SCREAMING_SNAKE_CASE")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TestEnum {
+    Foo,
+    BarBaz = 5,
+}
+
+#[test]
+fn test_enum() {
+    let type_ = TestEnum::static_type();
+    assert_eq!(type_.name(), "MacroTestEnum");
+    assert!(type_.is_a(&glib::Type::BaseEnum));
+
+    let class = glib::EnumClass::new(type_).unwrap();
+    let value = class.get_value(5).unwrap();
+    assert_eq!(value.get_name(), "BAR_BAZ");
+    assert_eq!(value.get_nick(), "bar-baz");
+
+    let value = TestEnum::BarBaz.to_value();
+    assert_eq!(value.type_(), type_);
+    assert_eq!(value.get::<TestEnum>(), Ok(Some(TestEnum::BarBaz)));
+
+    let mut value = TestEnum::Foo.to_value();
+    unsafe {
+        glib::gobject_sys::g_value_set_enum(value.to_glib_none_mut().0, 42);
+    }
+    assert_eq!(value.get::<TestEnum>(), Ok(None));
+
+    assert_eq!(TestEnum::BarBaz.to_string(), "bar-baz");
+    assert_eq!("foo".parse::<TestEnum>().unwrap(), TestEnum::Foo);
+    assert!("baz".parse::<TestEnum>().is_err());
+}
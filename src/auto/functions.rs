@@ -633,18 +633,22 @@ pub fn get_system_data_dirs() -> Vec<std::path::PathBuf> {
     unsafe { FromGlibPtrContainer::from_glib_none(glib_sys::g_get_system_data_dirs()) }
 }
 
+#[must_use]
 pub fn get_user_cache_dir() -> Option<std::path::PathBuf> {
     unsafe { from_glib_none(glib_sys::g_get_user_cache_dir()) }
 }
 
+#[must_use]
 pub fn get_user_config_dir() -> Option<std::path::PathBuf> {
     unsafe { from_glib_none(glib_sys::g_get_user_config_dir()) }
 }
 
+#[must_use]
 pub fn get_user_data_dir() -> Option<std::path::PathBuf> {
     unsafe { from_glib_none(glib_sys::g_get_user_data_dir()) }
 }
 
+#[must_use]
 pub fn get_user_runtime_dir() -> Option<std::path::PathBuf> {
     unsafe { from_glib_none(glib_sys::g_get_user_runtime_dir()) }
 }
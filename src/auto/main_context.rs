@@ -29,10 +29,6 @@ impl MainContext {
     //    unsafe { TODO: call glib_sys:g_main_context_add_poll() }
     //}
 
-    //pub fn check(&self, max_priority: i32, fds: /*Ignored*/&[&PollFD]) -> bool {
-    //    unsafe { TODO: call glib_sys:g_main_context_check() }
-    //}
-
     pub fn dispatch(&self) {
         unsafe {
             glib_sys::g_main_context_dispatch(self.to_glib_none().0);
@@ -80,10 +76,6 @@ impl MainContext {
         }
     }
 
-    //pub fn query(&self, max_priority: i32, fds: /*Ignored*/Vec<PollFD>) -> (i32, i32) {
-    //    unsafe { TODO: call glib_sys:g_main_context_query() }
-    //}
-
     pub fn release(&self) {
         unsafe {
             glib_sys::g_main_context_release(self.to_glib_none().0);
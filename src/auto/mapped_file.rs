@@ -0,0 +1,62 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// DO NOT EDIT
+
+use glib_sys;
+use std;
+use std::ptr;
+use translate::*;
+use Bytes;
+use Error;
+
+glib_wrapper! {
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct MappedFile(Shared<glib_sys::GMappedFile>);
+
+    match fn {
+        ref => |ptr| glib_sys::g_mapped_file_ref(ptr),
+        unref => |ptr| glib_sys::g_mapped_file_unref(ptr),
+        get_type => || glib_sys::g_mapped_file_get_type(),
+    }
+}
+
+impl MappedFile {
+    pub fn new<P: AsRef<std::path::Path>>(
+        filename: P,
+        writable: bool,
+    ) -> Result<MappedFile, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_mapped_file_new(
+                filename.as_ref().to_glib_none().0,
+                writable.to_glib(),
+                &mut error,
+            );
+            if error.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    pub fn new_from_fd(fd: i32, writable: bool) -> Result<MappedFile, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_mapped_file_new_from_fd(fd, writable.to_glib(), &mut error);
+            if error.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    pub fn get_bytes(&self) -> Bytes {
+        unsafe { from_glib_full(glib_sys::g_mapped_file_get_bytes(self.to_glib_none().0)) }
+    }
+
+    pub fn get_length(&self) -> usize {
+        unsafe { glib_sys::g_mapped_file_get_length(self.to_glib_none().0) }
+    }
+}
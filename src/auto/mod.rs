@@ -17,6 +17,9 @@ pub use self::main_context::MainContext;
 mod main_loop;
 pub use self::main_loop::MainLoop;
 
+mod mapped_file;
+pub use self::mapped_file::MappedFile;
+
 mod source;
 pub use self::source::Source;
 
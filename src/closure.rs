@@ -10,9 +10,12 @@ use std::slice;
 
 use libc::{c_uint, c_void};
 
+use glib_sys;
 use gobject_sys;
 use translate::{from_glib_none, mut_override, ToGlibPtr, ToGlibPtrMut, Uninitialized};
 use types::Type;
+use value::FromValueOptional;
+use BoolError;
 use ToValue;
 use Value;
 
@@ -94,8 +97,16 @@ impl Closure {
         from_glib_none(closure)
     }
 
+    /// Invokes the closure with the given arguments.
+    ///
+    /// Returns the return value of the closure, or `None` if it didn't return
+    /// anything. Does nothing and returns an error if the closure was invalidated.
     #[allow(clippy::redundant_closure)]
-    pub fn invoke(&self, values: &[&dyn ToValue]) -> Option<Value> {
+    pub fn invoke(&self, values: &[&dyn ToValue]) -> Result<Option<Value>, BoolError> {
+        if self.is_invalidated() {
+            return Err(glib_bool_error!("Closure was invalidated"));
+        }
+
         let mut result = unsafe { Value::uninitialized() };
 
         let v_args: Vec<Value>;
@@ -120,9 +131,45 @@ impl Closure {
             );
         }
         if result.type_() == Type::Invalid {
-            None
+            Ok(None)
         } else {
-            Some(result)
+            Ok(Some(result))
+        }
+    }
+
+    /// Invalidates the closure.
+    ///
+    /// Afterwards the closure can't be invoked anymore.
+    pub fn invalidate(&self) {
+        unsafe {
+            gobject_sys::g_closure_invalidate(self.to_glib_none().0);
+        }
+    }
+
+    /// Returns `true` if the closure was invalidated.
+    pub fn is_invalidated(&self) -> bool {
+        // There is no accessor for the `is_invalid` flag in the C API, and the sys
+        // crate can't express the bitfields at the beginning of the GClosure:
+        //
+        //   guint ref_count : 15; guint meta_marshal_nouse : 1; guint n_guards : 1;
+        //   guint n_fnotifiers : 2; guint n_inotifiers : 8; guint in_inotify : 1;
+        //   guint floating : 1; guint derivative_flag : 1; guint in_marshal : 1;
+        //   guint is_invalid : 1;
+        //
+        // These fill exactly one guint and `is_invalid` is the last one. All ABIs
+        // supported by GLib allocate bitfields starting at the least significant bit
+        // on little endian and at the most significant bit on big endian targets,
+        // which makes it the most or least significant bit respectively. GLib itself
+        // reads the flags atomically as one integer too.
+        #[cfg(target_endian = "little")]
+        const IS_INVALID: u32 = 1 << 31;
+        #[cfg(target_endian = "big")]
+        const IS_INVALID: u32 = 1;
+
+        unsafe {
+            let ptr: *mut gobject_sys::GClosure = self.to_glib_none().0;
+            let flags = glib_sys::g_atomic_int_get(ptr as *const i32) as u32;
+            flags & IS_INVALID != 0
         }
     }
 }
@@ -130,6 +177,102 @@ impl Closure {
 unsafe impl Send for Closure {}
 unsafe impl Sync for Closure {}
 
+#[doc(hidden)]
+pub trait IntoClosureReturnValue {
+    fn into_closure_return_value(self) -> Option<Value>;
+}
+
+impl IntoClosureReturnValue for () {
+    fn into_closure_return_value(self) -> Option<Value> {
+        None
+    }
+}
+
+impl<T: ToValue> IntoClosureReturnValue for T {
+    fn into_closure_return_value(self) -> Option<Value> {
+        Some(self.to_value())
+    }
+}
+
+// Used by `closure_local!`, which is called from the C marshaller and must not panic
+#[doc(hidden)]
+pub fn get_closure_argument<'a, T: FromValueOptional<'a>>(
+    value: Option<&'a Value>,
+    name: &str,
+) -> Option<T> {
+    let value = match value {
+        Some(value) => value,
+        None => {
+            ::log_critical(&format!("Missing closure argument `{}`", name));
+            return None;
+        }
+    };
+
+    match value.get::<T>() {
+        Ok(Some(value)) => Some(value),
+        Ok(None) => {
+            ::log_critical(&format!("Closure argument `{}` is None", name));
+            None
+        }
+        Err(err) => {
+            ::log_critical(&format!("Wrong type for closure argument `{}`: {}", name, err));
+            None
+        }
+    }
+}
+
+/// Creates a [`Closure`] with typed arguments that can capture values that are not `Send`.
+///
+/// The closure is created with [`Closure::new_local`] and panics if it is invoked from
+/// another thread than the one it was created on. The arguments are extracted from the
+/// `Value`s the closure is invoked with. If they are missing, `None` or have the wrong type,
+/// a critical is logged and the closure returns `None` without running the body. Additional
+/// arguments are ignored. The return value of the body is converted into a `Value`, unless
+/// it is `()`.
+///
+/// Like with the [`clone!`] macro, variables can be captured as strong or weak references
+/// before the closure. If upgrading a weak reference fails, the closure returns `None`
+/// without running the body.
+///
+/// ```
+/// # use glib::closure_local;
+/// use std::rc::Rc;
+///
+/// let suffix = Rc::new(String::from("!"));
+/// let closure = closure_local!(@strong suffix => move |s: String, n: i32| {
+///     format!("{}{}{}", s, n, suffix)
+/// });
+///
+/// let ret = closure.invoke(&[&"test", &1]).unwrap().unwrap();
+/// assert_eq!(ret.get::<String>(), Ok(Some(String::from("test1!"))));
+/// ```
+///
+/// [`Closure`]: closure/struct.Closure.html
+/// [`Closure::new_local`]: closure/struct.Closure.html#method.new_local
+/// [`clone!`]: macro.clone.html
+#[macro_export]
+macro_rules! closure_local {
+    ($($(@ $strength:ident $variable:ident),+ =>)? $(move)? || $body:expr) => {
+        $crate::closure_local!($($(@ $strength $variable),+ =>)? move | | $body)
+    };
+    ($($(@ $strength:ident $variable:ident),+ =>)? $(move)? |$($arg:ident: $ty:ty),* $(,)?| $body:expr) => {{
+        $($( $crate::to_type_before!(@ $strength $variable); )+)?
+        $crate::Closure::new_local(move |values: &[$crate::Value]| {
+            $($( $crate::to_type_after!(@ $strength $variable, || None); )+)?
+            #[allow(unused_mut, unused_variables)]
+            let mut values = values.iter();
+            $(
+                let $arg: $ty =
+                    match $crate::closure::get_closure_argument(values.next(), stringify!($arg)) {
+                        Some(arg) => arg,
+                        None => return None,
+                    };
+            )*
+            $crate::closure::IntoClosureReturnValue::into_closure_return_value($body)
+        })
+    }};
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::atomic::{AtomicUsize, Ordering};
@@ -162,17 +305,100 @@ mod tests {
             assert_eq!(int_arg, Ok(42));
             None
         });
-        let result = closure.invoke(&[&"test".to_string(), &42]);
+        let result = closure.invoke(&[&"test".to_string(), &42]).unwrap();
         assert!(result.is_none());
         assert_eq!(call_count.load(Ordering::Relaxed), 1);
 
-        let result = closure.invoke(&[&"test".to_string(), &42]);
+        let result = closure.invoke(&[&"test".to_string(), &42]).unwrap();
         assert!(result.is_none());
         assert_eq!(call_count.load(Ordering::Relaxed), 2);
 
         let closure = Closure::new(closure_fn);
-        let result = closure.invoke(&[&"test".to_string(), &42]);
+        let result = closure.invoke(&[&"test".to_string(), &42]).unwrap();
         let int_res = result.map(|result| result.get_some::<i32>());
         assert_eq!(int_res, Some(Ok(24)));
     }
+
+    #[test]
+    fn test_closure_invalidate() {
+        let call_count = Arc::new(AtomicUsize::new(0));
+
+        let count = call_count.clone();
+        let closure = Closure::new(move |_values| {
+            count.fetch_add(1, Ordering::Relaxed);
+            Some(1.to_value())
+        });
+        assert!(!closure.is_invalidated());
+        assert!(closure.invoke(&[]).unwrap().is_some());
+
+        closure.invalidate();
+        assert!(closure.is_invalidated());
+        assert!(closure.invoke(&[]).is_err());
+        assert_eq!(call_count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_closure_clone() {
+        struct DropCounter(Arc<AtomicUsize>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let call_count = Arc::new(AtomicUsize::new(0));
+        let drop_count = Arc::new(AtomicUsize::new(0));
+
+        let count = call_count.clone();
+        let counter = DropCounter(drop_count.clone());
+        let closure = Closure::new(move |_values| {
+            let _ = &counter;
+            count.fetch_add(1, Ordering::Relaxed);
+            None
+        });
+
+        let closure_clone = closure.clone();
+        assert_eq!(closure, closure_clone);
+
+        closure.invoke(&[]).unwrap();
+        drop(closure);
+        assert_eq!(drop_count.load(Ordering::Relaxed), 0);
+
+        closure_clone.invoke(&[]).unwrap();
+        assert_eq!(call_count.load(Ordering::Relaxed), 2);
+
+        drop(closure_clone);
+        assert_eq!(drop_count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_closure_local() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let count = Rc::new(Cell::new(0));
+        let closure = closure_local!(@strong count => move |a: i32, b: String| {
+            count.set(count.get() + 1);
+            format!("{}-{}", b, a)
+        });
+        let result = closure.invoke(&[&42, &"test"]).unwrap().unwrap();
+        assert_eq!(result.get::<String>(), Ok(Some("test-42".to_string())));
+        assert_eq!(count.get(), 1);
+
+        let weak_count = Rc::new(Cell::new(0));
+        let closure = closure_local!(@weak weak_count => move || {
+            weak_count.set(weak_count.get() + 1);
+        });
+        assert!(closure.invoke(&[]).unwrap().is_none());
+        assert_eq!(weak_count.get(), 1);
+        drop(weak_count);
+        assert!(closure.invoke(&[]).unwrap().is_none());
+
+        let closure = closure_local!(move |a: i32| a + 1);
+        let result = closure.invoke(&[&1]).unwrap().unwrap();
+        assert_eq!(result.get::<i32>(), Ok(Some(2)));
+        assert!(closure.invoke(&[]).unwrap().is_none());
+        assert!(closure.invoke(&[&"not an int"]).unwrap().is_none());
+    }
 }
@@ -0,0 +1,80 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use std::ffi::{OsStr, OsString};
+use translate::*;
+
+/// A snapshot of environment variables.
+///
+/// The variables are stored as `NAME=value` strings like returned by
+/// [`get_environ`], which are not necessarily valid UTF-8.
+///
+/// Modifying the snapshot via [`set`] and [`unset`] does not change the
+/// environment of the current process.
+///
+/// [`get_environ`]: fn.get_environ.html
+/// [`set`]: #method.set
+/// [`unset`]: #method.unset
+#[derive(Debug, Default)]
+pub struct Environment(Vec<OsString>);
+
+impl Environment {
+    /// Creates a snapshot of the environment of the current process.
+    pub fn current() -> Self {
+        Environment(::get_environ())
+    }
+
+    /// Returns the value of the variable `name`, if it is set.
+    pub fn get<K: AsRef<OsStr>>(&self, name: K) -> Option<OsString> {
+        ::environ_getenv(&self.0, name)
+    }
+
+    /// Sets the variable `name` to `value`, replacing any previous value.
+    pub fn set<K: AsRef<OsStr>, V: AsRef<OsStr>>(self, name: K, value: V) -> Self {
+        unsafe {
+            let envp = glib_sys::g_environ_setenv(
+                self.0[..].to_glib_full(),
+                name.as_ref().to_glib_none().0,
+                value.as_ref().to_glib_none().0,
+                glib_sys::GTRUE,
+            );
+            Environment(FromGlibPtrContainer::from_glib_full(envp))
+        }
+    }
+
+    /// Removes the variable `name`.
+    pub fn unset<K: AsRef<OsStr>>(self, name: K) -> Self {
+        unsafe {
+            let envp = glib_sys::g_environ_unsetenv(
+                self.0[..].to_glib_full(),
+                name.as_ref().to_glib_none().0,
+            );
+            Environment(FromGlibPtrContainer::from_glib_full(envp))
+        }
+    }
+
+    /// Returns a copy of the variables as `NAME=value` strings.
+    pub fn as_snapshot(&self) -> Vec<OsString> {
+        self.0.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_environment() {
+        let env = Environment::default().set("FOO", "1").set("BAR", "2");
+        assert_eq!(env.get("FOO"), Some(OsString::from("1")));
+        assert_eq!(env.get("BAR"), Some(OsString::from("2")));
+
+        let env = env.set("FOO", "3").unset("BAR");
+        assert_eq!(env.get("FOO"), Some(OsString::from("3")));
+        assert_eq!(env.get("BAR"), None);
+
+        assert_eq!(env.as_snapshot(), vec![OsString::from("FOO=3")]);
+    }
+}
@@ -60,6 +60,29 @@ impl SetValue for BindingFlags {
     }
 }
 
+bitflags! {
+    pub struct ConnectFlags: u32 {
+        const AFTER = 1;
+        const SWAPPED = 2;
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for ConnectFlags {
+    type GlibType = gobject_sys::GConnectFlags;
+
+    fn to_glib(&self) -> gobject_sys::GConnectFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<gobject_sys::GConnectFlags> for ConnectFlags {
+    fn from_glib(value: gobject_sys::GConnectFlags) -> ConnectFlags {
+        ConnectFlags::from_bits_truncate(value)
+    }
+}
+
 bitflags! {
     pub struct ParamFlags: u32 {
         const READABLE = 1;
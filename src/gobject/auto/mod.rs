@@ -5,10 +5,20 @@
 mod binding;
 pub use self::binding::{Binding, BindingClass};
 
+mod type_module;
+pub use self::type_module::TypeModuleExt;
+pub use self::type_module::{TypeModule, TypeModuleClass, NONE_TYPE_MODULE};
+
+mod type_plugin;
+pub use self::type_plugin::TypePlugin;
+
 mod flags;
 pub use self::flags::BindingFlags;
+pub use self::flags::ConnectFlags;
 pub use self::flags::ParamFlags;
 pub use self::flags::SignalFlags;
 
 #[doc(hidden)]
-pub mod traits {}
+pub mod traits {
+    pub use super::TypeModuleExt;
+}
@@ -0,0 +1,20 @@
+// This file was generated by gir (https://github.com/gtk-rs/gir)
+// from gir-files (https://github.com/gtk-rs/gir-files)
+// DO NOT EDIT
+
+use gobject_sys;
+use std::fmt;
+
+glib_wrapper! {
+    pub struct TypePlugin(Interface<gobject_sys::GTypePlugin>);
+
+    match fn {
+        get_type => || gobject_sys::g_type_plugin_get_type(),
+    }
+}
+
+impl fmt::Display for TypePlugin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TypePlugin")
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use gobject_sys;
+use std::ffi::CStr;
+use translate::*;
+use Binding;
+use BindingFlags;
+use Object;
+
+impl Binding {
+    /// Returns the source object of the binding, if it is still alive.
+    pub fn source_object(&self) -> Option<Object> {
+        self.get_source()
+    }
+
+    /// Returns the name of the bound property on the source object.
+    pub fn source_property(&self) -> &str {
+        unsafe {
+            let ptr = gobject_sys::g_binding_get_source_property(self.to_glib_none().0);
+            assert!(!ptr.is_null());
+            CStr::from_ptr(ptr).to_str().unwrap()
+        }
+    }
+
+    /// Returns the target object of the binding, if it is still alive.
+    pub fn target_object(&self) -> Option<Object> {
+        self.get_target()
+    }
+
+    /// Returns the name of the bound property on the target object.
+    pub fn target_property(&self) -> &str {
+        unsafe {
+            let ptr = gobject_sys::g_binding_get_target_property(self.to_glib_none().0);
+            assert!(!ptr.is_null());
+            CStr::from_ptr(ptr).to_str().unwrap()
+        }
+    }
+
+    /// Returns the flags the binding was created with.
+    pub fn flags(&self) -> BindingFlags {
+        self.get_flags()
+    }
+}
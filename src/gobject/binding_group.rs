@@ -0,0 +1,153 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use std::cell::RefCell;
+use std::fmt;
+use Binding;
+use BindingFlags;
+use Cast;
+use IsA;
+use Object;
+use ObjectExt;
+use WeakRef;
+
+struct BindingEntry {
+    source_property: String,
+    target: WeakRef<Object>,
+    target_property: String,
+    flags: BindingFlags,
+    binding: Option<Binding>,
+}
+
+impl BindingEntry {
+    fn bind(&mut self, source: &Object) {
+        let target = match self.target.upgrade() {
+            Some(target) => target,
+            None => return,
+        };
+
+        self.binding = source
+            .bind_property(self.source_property.as_str(), &target, self.target_property.as_str())
+            .flags(self.flags)
+            .build();
+    }
+
+    fn unbind(&mut self) {
+        if let Some(binding) = self.binding.take() {
+            // The binding is already unbound if the source or target are gone.
+            if binding.get_source().is_some() && binding.get_target().is_some() {
+                binding.unbind();
+            }
+        }
+    }
+}
+
+/// A group of property bindings that all share the same source object.
+///
+/// Bindings added via [`bind`] are applied to the current source object and
+/// moved to the new source whenever it is changed via [`set_source`]. Bindings
+/// that are added while no source is set are applied once a source is set.
+///
+/// The source and target objects are only weakly referenced.
+///
+/// [`bind`]: #method.bind
+/// [`set_source`]: #method.set_source
+pub struct BindingGroup {
+    source: RefCell<WeakRef<Object>>,
+    entries: RefCell<Vec<BindingEntry>>,
+}
+
+impl BindingGroup {
+    /// Create a new, empty binding group without source object.
+    pub fn new() -> Self {
+        BindingGroup {
+            source: RefCell::new(WeakRef::new()),
+            entries: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Bind `source_property` of the source object to `target_property` of `target`.
+    ///
+    /// If no source object is set yet, the binding is created once it is set.
+    pub fn bind<T: IsA<Object>>(
+        &self,
+        source_property: &str,
+        target: &T,
+        target_property: &str,
+        flags: BindingFlags,
+    ) {
+        let mut entry = BindingEntry {
+            source_property: String::from(source_property),
+            target: target.upcast_ref::<Object>().downgrade(),
+            target_property: String::from(target_property),
+            flags,
+            binding: None,
+        };
+
+        if let Some(source) = self.dup_source() {
+            entry.bind(&source);
+        }
+
+        self.entries.borrow_mut().push(entry);
+    }
+
+    /// Set the source object of all bindings in the group.
+    ///
+    /// All bindings to the previous source object are removed and, if `source`
+    /// is not `None`, recreated for the new source object.
+    pub fn set_source(&self, source: Option<&Object>) {
+        // Don't hold the borrow while (un)binding: with `SYNC_CREATE` binding
+        // notifies the target, and its handlers might call back into the group.
+        let mut entries = self.entries.replace(Vec::new());
+
+        // Drop entries whose target is gone, they can't be bound anymore.
+        entries.retain(|entry| entry.target.upgrade().is_some());
+
+        for entry in entries.iter_mut() {
+            entry.unbind();
+        }
+
+        self.source.replace(source.map(ObjectExt::downgrade).unwrap_or_else(WeakRef::new));
+
+        if let Some(source) = source {
+            for entry in entries.iter_mut() {
+                entry.bind(source);
+            }
+        }
+
+        // Keep entries that were added in the meantime, they are already bound
+        // to the new source.
+        let mut current = self.entries.borrow_mut();
+        entries.append(&mut current);
+        *current = entries;
+    }
+
+    /// Returns a new reference to the source object, if it is set and still alive.
+    pub fn dup_source(&self) -> Option<Object> {
+        self.source.borrow().upgrade()
+    }
+}
+
+impl Default for BindingGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for BindingGroup {
+    fn drop(&mut self) {
+        for entry in self.entries.get_mut().iter_mut() {
+            entry.unbind();
+        }
+    }
+}
+
+impl fmt::Debug for BindingGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BindingGroup")
+            .field("source", &self.dup_source())
+            .field("n_bindings", &self.entries.borrow().len())
+            .finish()
+    }
+}
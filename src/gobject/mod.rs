@@ -5,6 +5,9 @@
 //! GObject bindings
 
 pub mod auto;
+mod binding;
+mod binding_group;
+pub use self::binding_group::BindingGroup;
 
 pub use self::auto::*;
 //pub use self::auto::functions::*;
@@ -58,6 +58,31 @@ impl GString {
         };
         cstr.to_str().unwrap()
     }
+
+    /// Converts the NUL-terminated string `ptr` to a `String`, replacing
+    /// invalid UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    pub unsafe fn from_utf8_lossy(ptr: *const c_char) -> String {
+        assert!(!ptr.is_null());
+
+        let mut res = String::new();
+        let mut start = ptr;
+        loop {
+            let mut end = ptr::null();
+            let valid = glib_sys::g_utf8_validate(start, -1, &mut end);
+            let len = end as usize - start as usize;
+            let bytes = slice::from_raw_parts(start as *const u8, len);
+            res.push_str(std::str::from_utf8_unchecked(bytes));
+
+            if valid != glib_sys::GFALSE || *end == 0 {
+                break;
+            }
+
+            res.push('\u{FFFD}');
+            start = glib_sys::g_utf8_find_next_char(end, ptr::null());
+        }
+
+        res
+    }
 }
 
 impl Drop for GString {
@@ -427,6 +452,17 @@ mod tests {
     use gstring::GString;
     use std::ffi::CString;
 
+    #[test]
+    fn test_from_utf8_lossy() {
+        let valid = CString::new("foo ä").unwrap();
+        let invalid = CString::new(&b"foo \xe2\x82 bar \xff"[..]).unwrap();
+
+        unsafe {
+            assert_eq!(GString::from_utf8_lossy(valid.as_ptr()), "foo ä");
+            assert_eq!(GString::from_utf8_lossy(invalid.as_ptr()), "foo \u{FFFD} bar \u{FFFD}");
+        }
+    }
+
     #[test]
     fn test_gstring() {
         let data = CString::new("foo").unwrap();
@@ -0,0 +1,77 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+#[cfg(unix)]
+use libc;
+use std::fmt;
+use IOCondition;
+
+impl IOCondition {
+    /// Converts the `revents` of a `libc::pollfd` to the corresponding conditions.
+    #[cfg(unix)]
+    pub fn from_poll_revents(revents: i16) -> IOCondition {
+        let mut condition = IOCondition::empty();
+        for &(flag, poll_flag) in &[
+            (IOCondition::IN, libc::POLLIN),
+            (IOCondition::OUT, libc::POLLOUT),
+            (IOCondition::PRI, libc::POLLPRI),
+            (IOCondition::ERR, libc::POLLERR),
+            (IOCondition::HUP, libc::POLLHUP),
+            (IOCondition::NVAL, libc::POLLNVAL),
+        ] {
+            if revents & poll_flag != 0 {
+                condition |= flag;
+            }
+        }
+        condition
+    }
+}
+
+/// Formats the set conditions as lowercase names joined with `|`, e.g. `in|out`.
+impl fmt::Display for IOCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+        for &(flag, name) in &[
+            (IOCondition::IN, "in"),
+            (IOCondition::OUT, "out"),
+            (IOCondition::PRI, "pri"),
+            (IOCondition::ERR, "err"),
+            (IOCondition::HUP, "hup"),
+            (IOCondition::NVAL, "nval"),
+        ] {
+            if self.contains(flag) {
+                if !first {
+                    f.write_str("|")?;
+                }
+                f.write_str(name)?;
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(IOCondition::empty().to_string(), "");
+        assert_eq!(IOCondition::IN.to_string(), "in");
+        assert_eq!((IOCondition::IN | IOCondition::OUT).to_string(), "in|out");
+        assert_eq!((IOCondition::HUP | IOCondition::ERR).to_string(), "err|hup");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_from_poll_revents() {
+        assert_eq!(IOCondition::from_poll_revents(0), IOCondition::empty());
+        assert_eq!(
+            IOCondition::from_poll_revents(libc::POLLIN | libc::POLLHUP),
+            IOCondition::IN | IOCondition::HUP
+        );
+        assert_eq!(IOCondition::from_poll_revents(libc::POLLNVAL), IOCondition::NVAL);
+    }
+}
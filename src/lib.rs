@@ -30,6 +30,8 @@
 //! StaticType`.
 //!
 //! [`Variant`](variant/index.html) can carry values of `T: StaticVariantType`.
+//! With the `serde` feature enabled, [`variant_serde`](variant_serde/index.html)
+//! converts between `Variant` and any type implementing the `serde` traits.
 //!
 //! # Errors
 //!
@@ -73,6 +75,11 @@
 //! The [`translate`](translate/index.html) module defines and partly implements
 //! conversions between high level Rust types (including the aforementioned
 //! wrappers) and their FFI counterparts.
+//!
+//! The raw FFI bindings are re-exported as [`glib_sys`](../glib_sys/index.html)
+//! and [`gobject_sys`](../gobject_sys/index.html), so crates that need them
+//! don't have to depend on `glib-sys` or `gobject-sys` directly and keep their
+//! versions in sync.
 
 #![allow(clippy::doc_markdown)]
 #![allow(clippy::unreadable_literal)]
@@ -82,9 +89,9 @@ extern crate bitflags;
 extern crate libc;
 extern crate once_cell;
 
-#[doc(hidden)]
+/// Raw FFI bindings to GLib, as used by this crate.
 pub extern crate glib_sys;
-#[doc(hidden)]
+/// Raw FFI bindings to GObject, as used by this crate.
 pub extern crate gobject_sys;
 
 extern crate futures_channel;
@@ -93,26 +100,32 @@ extern crate futures_executor;
 extern crate futures_task;
 extern crate futures_util;
 
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+#[macro_use]
+extern crate serde_derive;
+
 pub use byte_array::ByteArray;
 pub use bytes::Bytes;
 pub use closure::Closure;
 pub use error::{BoolError, Error};
 pub use file_error::FileError;
 pub use object::{
-    Cast, InitiallyUnowned, InitiallyUnownedClass, IsA, IsClassFor, Object, ObjectClass, ObjectExt,
-    ObjectType, SendWeakRef, WeakRef,
+    Cast, FreezeNotifyGuard, InitiallyUnowned, InitiallyUnownedClass, InterfaceRef, IsA, IsClassFor,
+    Object, ObjectClass, ObjectExt, ObjectType, PropertyError, SendWeakRef, TypedObject, WeakRef,
 };
 pub use signal::{
     signal_handler_block, signal_handler_disconnect, signal_handler_unblock,
-    signal_stop_emission_by_name, SignalHandlerId,
+    signal_stop_emission_by_name, EmitError, SignalHandlerId, SignalInfo,
 };
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 pub use string::String;
 
 pub use enums::{EnumClass, EnumValue, FlagsBuilder, FlagsClass, FlagsValue, UserDirectory};
 pub use time_val::{get_current_time, TimeVal};
-pub use types::{StaticType, Type};
-pub use value::{SendValue, ToSendValue, ToValue, TypedValue, Value};
+pub use types::{StaticType, Type, TypeInfo, TypeName};
+pub use value::{register_value_transform, SendValue, ToSendValue, ToValue, TypedValue, Value};
 pub use variant::{StaticVariantType, ToVariant, Variant};
 pub use variant_type::{VariantTy, VariantType};
 
@@ -151,6 +164,7 @@ mod enums;
 mod file_error;
 mod functions;
 pub use functions::*;
+mod io_condition;
 mod key_file;
 pub mod prelude;
 pub mod signal;
@@ -161,18 +175,37 @@ mod time_val;
 pub mod translate;
 mod gstring;
 pub use gstring::GString;
+mod strv_array;
+pub use strv_array::StrvArray;
+mod environment;
+pub use environment::Environment;
+mod uri_params;
+pub use uri_params::{UriParamsFlags, UriParamsIter};
+mod uuid;
+pub use uuid::Uuid;
+mod mapped_file;
+mod markup;
+pub use markup::markup_to_text;
+mod temp_file;
+pub use temp_file::{TempDir, TempFile};
+mod object_factory;
+pub use object_factory::{FactoryError, ObjectFactory};
 pub mod types;
 mod utils;
 pub use utils::*;
 mod main_context;
+pub use main_context::{MainContextGuard, PollFD};
 mod main_context_channel;
 pub mod value;
 pub mod variant;
+#[cfg(feature = "serde")]
+pub mod variant_serde;
 mod variant_type;
-pub use main_context_channel::{Receiver, Sender, SyncSender};
+pub use main_context_channel::{Receiver, ReceiverGuard, Sender, SyncSender};
 mod date;
 pub use date::Date;
 mod value_array;
+#[allow(deprecated)]
 pub use value_array::ValueArray;
 mod param_spec;
 pub use param_spec::ParamSpec;
@@ -204,6 +237,22 @@ pub(crate) fn get_thread_id() -> usize {
     THREAD_ID.with(|&x| x)
 }
 
+// Log domain of criticals logged by this crate itself, e.g. from callbacks called by C code
+// that can't return an error and must not panic.
+const LOG_DOMAIN: &[u8] = b"glib-rs\0";
+
+pub(crate) fn log_critical(msg: &str) {
+    let msg = CString::new(msg).unwrap_or_else(|_| CString::new("<invalid message>").unwrap());
+    unsafe {
+        glib_sys::g_log(
+            LOG_DOMAIN.as_ptr() as *const _,
+            glib_sys::G_LOG_LEVEL_CRITICAL,
+            b"%s\0".as_ptr() as *const _,
+            msg.as_ptr(),
+        );
+    }
+}
+
 pub(crate) struct ThreadGuard<T> {
     thread_id: usize,
     value: T,
@@ -4,8 +4,14 @@
 
 use glib_sys::{self, gboolean, gpointer};
 use source::Priority;
+use std::fmt;
+use std::marker::PhantomData;
 use std::mem;
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, RawFd};
 use translate::*;
+use BoolError;
+use IOCondition;
 use MainContext;
 use Source;
 use SourceId;
@@ -24,6 +30,58 @@ impl MainContext {
         }
     }
 
+    /// Returns the file descriptors that need to be polled and the timeout until the next
+    /// dispatch for sources with at least `max_priority`.
+    ///
+    /// `fds` is resized to the number of file descriptors, which is also returned. Before
+    /// calling [`check`][check] the received events have to be stored in each `PollFD` with
+    /// [`set_revents`][set_revents].
+    ///
+    /// This is only needed when driving the context manually with [`prepare`][prepare],
+    /// `query`, [`check`][check] and [`dispatch`][dispatch] instead of
+    /// [`iteration`][iteration].
+    ///
+    /// [check]: struct.MainContext.html#method.check
+    /// [set_revents]: struct.PollFD.html#method.set_revents
+    /// [prepare]: struct.MainContext.html#method.prepare
+    /// [dispatch]: struct.MainContext.html#method.dispatch
+    /// [iteration]: struct.MainContext.html#method.iteration
+    pub fn query(&self, max_priority: i32, timeout: &mut i32, fds: &mut Vec<PollFD>) -> usize {
+        unsafe {
+            loop {
+                let n_fds = glib_sys::g_main_context_query(
+                    self.to_glib_none().0,
+                    max_priority,
+                    timeout,
+                    fds.as_mut_ptr() as *mut glib_sys::GPollFD,
+                    fds.len() as i32,
+                ) as usize;
+
+                if n_fds <= fds.len() {
+                    fds.truncate(n_fds);
+                    return n_fds;
+                }
+
+                fds.resize(n_fds, PollFD(mem::zeroed()));
+            }
+        }
+    }
+
+    /// Checks if any sources with at least `max_priority` are ready to be dispatched after
+    /// polling the file descriptors returned by [`query`][query].
+    ///
+    /// [query]: struct.MainContext.html#method.query
+    pub fn check(&self, max_priority: i32, fds: &[PollFD]) -> bool {
+        unsafe {
+            from_glib(glib_sys::g_main_context_check(
+                self.to_glib_none().0,
+                max_priority,
+                fds.as_ptr() as *mut glib_sys::GPollFD,
+                fds.len() as i32,
+            ))
+        }
+    }
+
     pub fn find_source_by_id(&self, source_id: &SourceId) -> Option<Source> {
         unsafe {
             from_glib_none(glib_sys::g_main_context_find_source_by_id(
@@ -123,6 +181,99 @@ impl MainContext {
         let _thread_default = ThreadDefaultContext::new(self);
         func()
     }
+
+    /// Returns `true` if this context is the thread default context of the current thread.
+    ///
+    /// If no context was pushed with [`push_thread_default`][push_thread_default] this is the
+    /// global default context, as returned by [`ref_thread_default`][ref_thread_default].
+    ///
+    /// [push_thread_default]: struct.MainContext.html#method.push_thread_default
+    /// [ref_thread_default]: struct.MainContext.html#method.ref_thread_default
+    pub fn is_thread_default(&self) -> bool {
+        let thread_default = MainContext::ref_thread_default();
+        self.to_glib_none().0 == thread_default.to_glib_none().0
+    }
+
+    /// Acquires ownership of the context for the current thread.
+    ///
+    /// This is needed when driving the context manually with [`iteration`][iteration] from
+    /// a custom event loop. Ownership is released again when the returned guard is dropped.
+    /// Fails if the context is currently owned by another thread.
+    ///
+    /// [iteration]: struct.MainContext.html#method.iteration
+    pub fn acquire_guard(&self) -> Result<MainContextGuard, BoolError> {
+        if self.acquire() {
+            Ok(MainContextGuard(self, PhantomData))
+        } else {
+            Err(glib_bool_error!(
+                "Failed to acquire ownership of main context, already acquired by another thread"
+            ))
+        }
+    }
+}
+
+/// Ownership of a `MainContext` acquired with
+/// [`MainContext::acquire_guard`](struct.MainContext.html#method.acquire_guard).
+///
+/// The context is released again when the guard is dropped. The guard can't be sent to
+/// other threads as the context has to be released by the thread that acquired it.
+#[derive(Debug)]
+#[must_use = "if unused the main context is released immediately"]
+pub struct MainContextGuard<'a>(&'a MainContext, PhantomData<*const ()>);
+
+impl<'a> Drop for MainContextGuard<'a> {
+    fn drop(&mut self) {
+        self.0.release();
+    }
+}
+
+/// A file descriptor to poll, as returned by
+/// [`MainContext::query`](struct.MainContext.html#method.query).
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct PollFD(glib_sys::GPollFD);
+
+impl PollFD {
+    #[cfg(unix)]
+    pub fn new(fd: RawFd, events: IOCondition) -> PollFD {
+        PollFD(glib_sys::GPollFD {
+            fd,
+            events: events.to_glib() as u16,
+            revents: 0,
+        })
+    }
+
+    /// Returns the events that should be polled for.
+    pub fn get_events(&self) -> IOCondition {
+        from_glib(u32::from(self.0.events))
+    }
+
+    /// Returns the events that were received when polling.
+    pub fn get_revents(&self) -> IOCondition {
+        from_glib(u32::from(self.0.revents))
+    }
+
+    /// Stores the events that were received when polling.
+    pub fn set_revents(&mut self, revents: IOCondition) {
+        self.0.revents = revents.to_glib() as u16;
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for PollFD {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.fd
+    }
+}
+
+impl fmt::Debug for PollFD {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PollFD")
+            .field("fd", &self.0.fd)
+            .field("events", &self.get_events())
+            .field("revents", &self.get_revents())
+            .finish()
+    }
 }
 
 struct ThreadDefaultContext<'a>(&'a MainContext);
@@ -160,6 +311,81 @@ mod tests {
         l.run();
     }
 
+    #[test]
+    fn test_invoke_local() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let c = MainContext::new();
+        assert!(c.acquire());
+
+        let called = Rc::new(Cell::new(false));
+        let called_clone = called.clone();
+        c.invoke_local(move || called_clone.set(true));
+        while c.pending() {
+            c.iteration(false);
+        }
+        assert!(called.get());
+
+        // Only the owner of the context is allowed to invoke non-Send closures
+        let c_clone = c.clone();
+        let res = thread::spawn(move || c_clone.invoke_local(|| ())).join();
+        assert!(res.is_err());
+
+        c.release();
+    }
+
+    #[test]
+    fn test_acquire_guard() {
+        let c = MainContext::new();
+
+        {
+            let _guard = c.acquire_guard().unwrap();
+            assert!(c.is_owner());
+
+            let c_clone = c.clone();
+            let res = thread::spawn(move || c_clone.acquire_guard().is_err())
+                .join()
+                .unwrap();
+            assert!(res);
+
+            c.wakeup();
+            assert!(!c.iteration(false));
+        }
+
+        assert!(!c.is_owner());
+    }
+
+    #[test]
+    fn test_manual_dispatch() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let c = MainContext::new();
+        let _guard = c.acquire_guard().unwrap();
+
+        let called = Arc::new(AtomicBool::new(false));
+        let called_clone = called.clone();
+        let source = ::idle_source_new(None, ::PRIORITY_DEFAULT, move || {
+            called_clone.store(true, Ordering::SeqCst);
+            ::Continue(false)
+        });
+        source.attach(Some(&c));
+
+        let (ready, priority) = c.prepare();
+        assert!(ready);
+
+        let mut timeout = -1;
+        let mut fds = Vec::new();
+        let n_fds = c.query(priority, &mut timeout, &mut fds);
+        assert_eq!(n_fds, fds.len());
+        assert_eq!(timeout, 0);
+
+        assert!(c.check(priority, &fds));
+        c.dispatch();
+        assert!(called.load(Ordering::SeqCst));
+    }
+
     fn is_same_context(a: &MainContext, b: &MainContext) -> bool {
         ptr::eq(a.to_glib_none().0, b.to_glib_none().0)
     }
@@ -207,4 +433,21 @@ mod tests {
             assert!(is_same_context(&a, &t));
         });
     }
+
+    #[test]
+    fn test_is_thread_default() {
+        thread::spawn(|| {
+            let a = MainContext::new();
+            assert!(MainContext::default().is_thread_default());
+            assert!(!a.is_thread_default());
+
+            a.with_thread_default(|| {
+                assert!(a.is_thread_default());
+                assert!(!MainContext::default().is_thread_default());
+            });
+            assert!(!a.is_thread_default());
+        })
+        .join()
+        .unwrap();
+    }
 }
@@ -5,6 +5,7 @@
 use glib_sys;
 use std::collections::VecDeque;
 use std::fmt;
+use std::marker::PhantomData;
 use std::mem;
 use std::ptr;
 use std::sync::mpsc;
@@ -30,10 +31,16 @@ struct ChannelInner<T> {
     queue: VecDeque<T>,
     source: ChannelSourceState,
     num_senders: usize,
+    closed: bool,
 }
 
 impl<T> ChannelInner<T> {
     fn receiver_disconnected(&self) -> bool {
+        // A closed channel behaves as if the receiver was gone
+        if self.closed {
+            return true;
+        }
+
         match self.source {
             ChannelSourceState::Destroyed => true,
             // Receiver exists but is already destroyed
@@ -78,6 +85,7 @@ impl<T> Channel<T> {
                 queue: VecDeque::new(),
                 source: ChannelSourceState::NotAttached,
                 num_senders: 0,
+                closed: false,
             }),
             bound.map(|bound| ChannelBound {
                 bound,
@@ -179,9 +187,26 @@ impl<T> Channel<T> {
         Ok(())
     }
 
+    fn close(&self) {
+        let mut inner = (self.0).0.lock().unwrap();
+        inner.closed = true;
+
+        // Wake up the GSource so that it removes itself, and all senders that are currently
+        // waiting for free space
+        inner.set_ready_time(0);
+        if let Some(ChannelBound { ref cond, .. }) = (self.0).1 {
+            cond.notify_all();
+        }
+    }
+
     fn try_recv(&self) -> Result<T, mpsc::TryRecvError> {
         let mut inner = (self.0).0.lock().unwrap();
 
+        // Items that are still queued are not delivered anymore after closing
+        if inner.closed {
+            return Err(mpsc::TryRecvError::Disconnected);
+        }
+
         // Pop item if we have any
         if let Some(item) = inner.queue.pop_front() {
             // Wake up a sender that is currently waiting, if any
@@ -312,6 +337,15 @@ impl<T> Sender<T> {
     pub fn send(&self, t: T) -> Result<(), mpsc::SendError<T>> {
         self.0.as_ref().expect("Sender with no channel").send(t)
     }
+
+    /// Closes the channel for all senders and the receiver.
+    ///
+    /// Items that were not received yet are dropped together with the receiver, which is
+    /// removed from its main context on the next dispatch. Sending to any sender of the
+    /// channel fails afterwards.
+    pub fn close(&self) {
+        self.0.as_ref().expect("Sender with no channel").close()
+    }
 }
 
 impl<T> Drop for Sender<T> {
@@ -368,6 +402,13 @@ impl<T> SyncSender<T> {
     pub fn try_send(&self, t: T) -> Result<(), mpsc::TrySendError<T>> {
         self.0.as_ref().expect("Sender with no channel").try_send(t)
     }
+
+    /// Closes the channel for all senders and the receiver.
+    ///
+    /// This behaves the same as [`Sender::close`](struct.Sender.html#method.close).
+    pub fn close(&self) {
+        self.0.as_ref().expect("Sender with no channel").close()
+    }
 }
 
 impl<T> Drop for SyncSender<T> {
@@ -487,6 +528,51 @@ impl<T> Receiver<T> {
             }
         }
     }
+
+    /// Attaches the receiver like [`attach`][attach] but returns a guard that removes the
+    /// receiver from the main context again when dropped.
+    ///
+    /// The guard has to be dropped on the thread that owns the main context.
+    ///
+    /// # Panics
+    ///
+    /// This function panics under the same conditions as [`attach`][attach].
+    ///
+    /// [attach]: #method.attach
+    pub fn attach_and_cancel<F: FnMut(T) -> Continue + 'static>(
+        self,
+        context: Option<&MainContext>,
+        func: F,
+    ) -> ReceiverGuard {
+        let context = match context {
+            Some(context) => context.clone(),
+            None => MainContext::ref_thread_default(),
+        };
+
+        let source_id = self.attach(Some(&context), func);
+        let source = context
+            .find_source_by_id(&source_id)
+            .expect("Attached receiver not found");
+
+        ReceiverGuard(source, PhantomData)
+    }
+}
+
+/// A guard that removes an attached [`Receiver`] from its main context when dropped.
+///
+/// See [`Receiver::attach_and_cancel`] for how to create such a guard.
+///
+/// [`Receiver`]: struct.Receiver.html
+/// [`Receiver::attach_and_cancel`]: struct.Receiver.html#method.attach_and_cancel
+// The receiver's closure is dropped together with the source, which has to happen on the
+// thread owning the main context, so the guard must not be Send
+#[derive(Debug)]
+pub struct ReceiverGuard(Source, PhantomData<*const ()>);
+
+impl Drop for ReceiverGuard {
+    fn drop(&mut self) {
+        self.0.destroy();
+    }
 }
 
 impl MainContext {
@@ -658,6 +744,60 @@ mod tests {
         assert_eq!(sender.send(1), Err(mpsc::SendError(1)));
     }
 
+    #[test]
+    fn test_receiver_guard() {
+        let c = MainContext::new();
+
+        c.acquire();
+
+        let (sender, receiver) = MainContext::channel::<i32>(Priority::default());
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received_clone = received.clone();
+        let guard = receiver.attach_and_cancel(Some(&c), move |item| {
+            received_clone.borrow_mut().push(item);
+            Continue(true)
+        });
+
+        sender.send(1).unwrap();
+        while c.pending() {
+            c.iteration(false);
+        }
+        assert_eq!(*received.borrow(), vec![1]);
+
+        drop(guard);
+        assert_eq!(sender.send(2), Err(mpsc::SendError(2)));
+        assert_eq!(*received.borrow(), vec![1]);
+    }
+
+    #[test]
+    fn test_close_sender() {
+        let c = MainContext::new();
+
+        c.acquire();
+
+        let (sender, receiver) = MainContext::channel::<i32>(Priority::default());
+        let sender_clone = sender.clone();
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received_clone = received.clone();
+        let source_id = receiver.attach(Some(&c), move |item| {
+            received_clone.borrow_mut().push(item);
+            Continue(true)
+        });
+
+        sender.send(1).unwrap();
+        sender.close();
+        assert_eq!(sender.send(2), Err(mpsc::SendError(2)));
+        assert_eq!(sender_clone.send(3), Err(mpsc::SendError(3)));
+
+        while c.pending() {
+            c.iteration(false);
+        }
+        assert!(received.borrow().is_empty());
+        assert!(c.find_source_by_id(&source_id).is_none());
+    }
+
     #[test]
     fn test_sync_channel() {
         let c = MainContext::new();
@@ -0,0 +1,48 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use std::slice;
+use translate::*;
+use MappedFile;
+
+impl MappedFile {
+    /// Returns the mapped contents.
+    ///
+    /// The contents stay valid as long as the `MappedFile` is alive, use
+    /// [`get_bytes`](#method.get_bytes) for keeping them alive independently.
+    pub fn get_contents(&self) -> &[u8] {
+        unsafe {
+            let len = self.get_length();
+            if len == 0 {
+                return &[];
+            }
+            let ptr = glib_sys::g_mapped_file_get_contents(self.to_glib_none().0);
+            slice::from_raw_parts(ptr as *const u8, len)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mapped_file() {
+        let path = ::get_tmp_dir()
+            .unwrap()
+            .join(format!("glib-mapped-file-test-{}", std::process::id()));
+        ::file_set_contents(&path, b"mapped contents").unwrap();
+
+        let file = MappedFile::new(&path, false).unwrap();
+        assert_eq!(file.get_length(), 15);
+        assert_eq!(file.get_contents(), b"mapped contents");
+        let bytes = file.get_bytes();
+        drop(file);
+        assert_eq!(&*bytes, b"mapped contents");
+
+        assert!(MappedFile::new(path.join("does-not-exist"), false).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use libc::{c_char, size_t};
+use std::ptr;
+use std::slice;
+use std::str;
+use translate::*;
+use Error;
+
+/// Strips all tags from Pango-style `markup` and returns the remaining text.
+///
+/// Entities like `&amp;` are replaced by the characters they stand for. The markup does
+/// not need to be wrapped in a `<markup>` element, but it has to be well-formed, otherwise
+/// a `MarkupError` is returned.
+///
+/// This only uses GLib's markup parser, so unlike `pango_parse_markup()` it neither
+/// validates tag names nor requires Pango.
+pub fn markup_to_text(markup: &str) -> Result<String, Error> {
+    unsafe extern "C" fn text(
+        _context: *mut glib_sys::GMarkupParseContext,
+        text: *const c_char,
+        text_len: size_t,
+        user_data: glib_sys::gpointer,
+        _error: *mut *mut glib_sys::GError,
+    ) {
+        let res = &mut *(user_data as *mut String);
+        // GLib only passes on text that it validated as UTF-8
+        let text = slice::from_raw_parts(text as *const u8, text_len);
+        res.push_str(str::from_utf8_unchecked(text));
+    }
+
+    let parser = glib_sys::GMarkupParser {
+        start_element: None,
+        end_element: None,
+        text: Some(text),
+        passthrough: None,
+        error: None,
+    };
+
+    let mut res = String::with_capacity(markup.len());
+    unsafe {
+        let context = glib_sys::g_markup_parse_context_new(
+            &parser,
+            0,
+            &mut res as *mut String as glib_sys::gpointer,
+            None,
+        );
+
+        let mut error = ptr::null_mut();
+        for chunk in &["<markup>", markup, "</markup>"] {
+            if glib_sys::g_markup_parse_context_parse(
+                context,
+                chunk.as_ptr() as *const c_char,
+                chunk.len() as isize,
+                &mut error,
+            ) == glib_sys::GFALSE
+            {
+                break;
+            }
+        }
+        if error.is_null() {
+            glib_sys::g_markup_parse_context_end_parse(context, &mut error);
+        }
+        glib_sys::g_markup_parse_context_free(context);
+
+        if error.is_null() {
+            Ok(res)
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markup_to_text() {
+        assert_eq!(markup_to_text("plain").unwrap(), "plain");
+        assert_eq!(
+            markup_to_text("<b>bold</b> and <span foreground=\"red\">red</span>").unwrap(),
+            "bold and red"
+        );
+        assert_eq!(markup_to_text("a &amp; b &lt;c&gt;").unwrap(), "a & b <c>");
+        assert_eq!(markup_to_text("<markup>wrapped</markup>").unwrap(), "wrapped");
+
+        assert!(markup_to_text("<b>unclosed").is_err());
+        assert!(markup_to_text("a & b").is_err());
+    }
+}
@@ -7,6 +7,8 @@
 use glib_sys;
 use gobject_sys;
 use std::cmp;
+use std::collections::{HashMap, HashSet};
+use std::ffi::CString;
 use std::fmt;
 use std::hash;
 use std::marker::PhantomData;
@@ -16,9 +18,10 @@ use std::ptr;
 use translate::*;
 use types::StaticType;
 
-use value::ToValue;
+use value::{FromValueOptional, ToValue};
 use BoolError;
 use Closure;
+use Quark;
 use SignalHandlerId;
 use Type;
 use Value;
@@ -1217,33 +1220,47 @@ glib_object_wrapper!(@object
     Object, GObject, GObjectClass, ObjectClass, @get_type gobject_sys::g_object_get_type()
 );
 
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}@{:p}", self.get_type(), self.as_ptr())
+    }
+}
+
 impl Object {
     pub fn new(type_: Type, properties: &[(&str, &dyn ToValue)]) -> Result<Object, BoolError> {
-        use std::ffi::CString;
+        let names = properties.iter().map(|&(name, _)| name).collect::<Vec<_>>();
+        let values = properties
+            .iter()
+            .map(|&(_, value)| value.to_value())
+            .collect::<Vec<_>>();
 
+        Object::new_with_properties(type_, &names, &values)
+    }
+
+    /// Creates a new instance of `type_` with the properties `names` set to
+    /// the corresponding `values`.
+    ///
+    /// `names` and `values` must have the same length.
+    pub fn new_with_properties(
+        type_: Type,
+        names: &[&str],
+        values: &[Value],
+    ) -> Result<Object, BoolError> {
         if !type_.is_a(&Object::static_type()) {
             return Err(glib_bool_error!("Can't instantiate non-GObject objects"));
         }
 
-        let params = properties
-            .iter()
-            .map(|&(name, value)| (CString::new(name).unwrap(), value.to_value()))
-            .collect::<Vec<_>>();
+        if names.len() != values.len() {
+            return Err(glib_bool_error!("Number of property names and values doesn't match"));
+        }
 
-        let params_c = params
+        let names = names
             .iter()
-            .map(|&(ref name, ref value)| gobject_sys::GParameter {
-                name: name.as_ptr(),
-                value: unsafe { *value.to_glib_none().0 },
-            })
+            .map(|name| CString::new(*name).unwrap())
             .collect::<Vec<_>>();
 
         unsafe {
-            let ptr = gobject_sys::g_object_newv(
-                type_.to_glib(),
-                params_c.len() as u32,
-                mut_override(params_c.as_ptr()),
-            );
+            let ptr = Object::new_internal(type_, &names, values);
             if ptr.is_null() {
                 Err(glib_bool_error!("Can't instantiate object"))
             } else if type_.is_a(&InitiallyUnowned::static_type()) {
@@ -1254,12 +1271,241 @@ impl Object {
             }
         }
     }
+
+    #[cfg(any(feature = "v2_54", feature = "dox"))]
+    unsafe fn new_internal(
+        type_: Type,
+        names: &[CString],
+        values: &[Value],
+    ) -> *mut gobject_sys::GObject {
+        let names_c = names.iter().map(|name| name.as_ptr()).collect::<Vec<_>>();
+
+        gobject_sys::g_object_new_with_properties(
+            type_.to_glib(),
+            names_c.len() as u32,
+            mut_override(names_c.as_ptr()),
+            values.as_ptr() as *const gobject_sys::GValue,
+        )
+    }
+
+    // `g_object_newv()` is deprecated since GLib 2.54 but the only option for older versions
+    #[cfg(not(any(feature = "v2_54", feature = "dox")))]
+    #[allow(deprecated)]
+    unsafe fn new_internal(
+        type_: Type,
+        names: &[CString],
+        values: &[Value],
+    ) -> *mut gobject_sys::GObject {
+        let params_c = names
+            .iter()
+            .zip(values)
+            .map(|(name, value)| gobject_sys::GParameter {
+                name: name.as_ptr(),
+                value: *value.to_glib_none().0,
+            })
+            .collect::<Vec<_>>();
+
+        gobject_sys::g_object_newv(
+            type_.to_glib(),
+            params_c.len() as u32,
+            mut_override(params_c.as_ptr()),
+        )
+    }
+
+    /// Takes ownership of the floating reference of `ptr`, or adds a new
+    /// reference if it has no floating reference, via `g_object_ref_sink()`.
+    ///
+    /// This is useful for wrapping C APIs that return objects with floating
+    /// references, e.g. newly created `GInitiallyUnowned` instances.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a valid, non-NULL pointer to a `GObject`.
+    pub unsafe fn ref_sink(ptr: *mut gobject_sys::GObject) -> Object {
+        assert!(!ptr.is_null());
+        from_glib_full(gobject_sys::g_object_ref_sink(ptr as *mut _) as *mut gobject_sys::GObject)
+    }
+}
+
+fn value_from_str(type_: Type, s: &str) -> Result<Value, BoolError> {
+    fn parse<T>(type_: Type, s: &str) -> Result<Value, BoolError>
+    where
+        T: std::str::FromStr + ToValue,
+        T::Err: fmt::Display,
+    {
+        s.trim().parse::<T>().map(|v| v.to_value()).map_err(|err| {
+            glib_bool_error!("Failed to parse '{}' as {}: {}", s, type_.name(), err)
+        })
+    }
+
+    let fundamental: Type = unsafe { from_glib(gobject_sys::g_type_fundamental(type_.to_glib())) };
+    match fundamental {
+        Type::Bool => match s.trim().to_ascii_lowercase().as_str() {
+            "true" | "yes" | "1" => Ok(true.to_value()),
+            "false" | "no" | "0" => Ok(false.to_value()),
+            _ => Err(glib_bool_error!("Failed to parse '{}' as {}", s, type_.name())),
+        },
+        Type::I8 => parse::<i8>(type_, s),
+        Type::U8 => parse::<u8>(type_, s),
+        Type::I32 => parse::<i32>(type_, s),
+        Type::U32 => parse::<u32>(type_, s),
+        Type::I64 => parse::<i64>(type_, s),
+        Type::U64 => parse::<u64>(type_, s),
+        Type::F32 => parse::<f32>(type_, s),
+        Type::F64 => parse::<f64>(type_, s),
+        Type::String => Ok(s.to_value()),
+        Type::BaseEnum => {
+            let class = ::EnumClass::new(type_).unwrap();
+            let s = s.trim();
+            class
+                .get_value_by_name(s)
+                .or_else(|| class.get_value_by_nick(s))
+                .map(|value| value.to_value())
+                .ok_or_else(|| glib_bool_error!("Invalid value '{}' for {}", s, type_.name()))
+        }
+        Type::BaseFlags => {
+            let class = ::FlagsClass::new(type_).unwrap();
+            let mut flags = 0;
+            for part in s.split('|').map(str::trim).filter(|part| !part.is_empty()) {
+                let value = class
+                    .get_value_by_name(part)
+                    .or_else(|| class.get_value_by_nick(part))
+                    .ok_or_else(|| {
+                        glib_bool_error!("Invalid value '{}' for {}", part, type_.name())
+                    })?;
+                flags |= value.get_value();
+            }
+
+            let mut value = Value::from_type(type_);
+            unsafe {
+                gobject_sys::g_value_set_flags(value.to_glib_none_mut().0, flags);
+            }
+            Ok(value)
+        }
+        _ => unsafe {
+            let mut value = Value::from_type(type_);
+            if from_glib(gobject_sys::g_value_transform(
+                s.to_value().to_glib_none().0,
+                value.to_glib_none_mut().0,
+            )) {
+                Ok(value)
+            } else {
+                Err(glib_bool_error!("Can't convert string to {}", type_.name()))
+            }
+        },
+    }
+}
+
+unsafe fn emit_internal(
+    obj: &ObjectRef,
+    type_: Type,
+    signal_name: &str,
+    args: &[&dyn ToValue],
+) -> Result<Option<Value>, ::EmitError> {
+    let mut signal_id = 0;
+    let mut signal_detail = 0;
+
+    let found: bool = from_glib(gobject_sys::g_signal_parse_name(
+        signal_name.to_glib_none().0,
+        type_.to_glib(),
+        &mut signal_id,
+        &mut signal_detail,
+        true.to_glib(),
+    ));
+
+    if !found {
+        return Err(::EmitError::UnknownSignal);
+    }
+
+    let mut details = mem::MaybeUninit::zeroed();
+    gobject_sys::g_signal_query(signal_id, details.as_mut_ptr());
+    let details = details.assume_init();
+    if details.signal_id != signal_id {
+        return Err(::EmitError::UnknownSignal);
+    }
+
+    if details.n_params != args.len() as u32 {
+        return Err(::EmitError::ArgumentCountMismatch {
+            expected: details.n_params as usize,
+            got: args.len(),
+        });
+    }
+
+    for (i, item) in args.iter().enumerate() {
+        let arg_type = *(details.param_types.add(i)) & (!gobject_sys::G_TYPE_FLAG_RESERVED_ID_BIT);
+        if arg_type != item.to_value_type().to_glib() {
+            return Err(::EmitError::ArgumentTypeMismatch {
+                index: i,
+                expected: from_glib(arg_type),
+                got: item.to_value_type(),
+            });
+        }
+    }
+
+    let mut v_args: Vec<Value>;
+    let mut s_args: [Value; 10] = mem::zeroed();
+    let self_v = {
+        let mut v = Value::uninitialized();
+        gobject_sys::g_value_init(v.to_glib_none_mut().0, type_.to_glib());
+        gobject_sys::g_value_set_object(v.to_glib_none_mut().0, obj.to_glib_none().0);
+        v
+    };
+    let args = if args.len() < 10 {
+        s_args[0] = self_v;
+        for (i, arg) in args.iter().enumerate() {
+            s_args[i + 1] = arg.to_value();
+        }
+        &s_args[0..=args.len()]
+    } else {
+        v_args = Vec::with_capacity(args.len() + 1);
+        v_args.push(self_v);
+        for arg in args {
+            v_args.push(arg.to_value());
+        }
+        v_args.as_slice()
+    };
+
+    // This is actually G_SIGNAL_TYPE_STATIC_SCOPE
+    let return_type = details.return_type & (!gobject_sys::G_TYPE_FLAG_RESERVED_ID_BIT);
+    let mut return_value = Value::uninitialized();
+    if return_type != gobject_sys::G_TYPE_NONE {
+        if gobject_sys::g_type_check_is_value_type(return_type) == glib_sys::GFALSE {
+            return Err(::EmitError::InvalidReturnType);
+        }
+        gobject_sys::g_value_init(return_value.to_glib_none_mut().0, return_type);
+    }
+
+    gobject_sys::g_signal_emitv(
+        mut_override(args.as_ptr()) as *mut gobject_sys::GValue,
+        signal_id,
+        signal_detail,
+        return_value.to_glib_none_mut().0,
+    );
+
+    if return_type == gobject_sys::G_TYPE_NONE {
+        return Ok(None);
+    }
+
+    let valid_type: bool = from_glib(gobject_sys::g_type_check_value_holds(
+        mut_override(return_value.to_glib_none().0),
+        return_type,
+    ));
+    if !valid_type {
+        return Err(::EmitError::InvalidReturnType);
+    }
+
+    Ok(Some(return_value))
 }
 
 pub trait ObjectExt: ObjectType {
     /// Returns `true` if the object is an instance of (can be cast to) `T`.
     fn is<T: StaticType>(&self) -> bool;
 
+    /// Returns the runtime type of the object instance.
+    ///
+    /// This is the most-derived type of the instance, which can differ from
+    /// the `StaticType::static_type()` of the Rust wrapper, e.g. for a
+    /// subclass instance referenced via a `glib::Object`.
     fn get_type(&self) -> Type;
     fn get_object_class(&self) -> &ObjectClass;
 
@@ -1268,7 +1514,40 @@ pub trait ObjectExt: ObjectType {
         property_name: N,
         value: &dyn ToValue,
     ) -> Result<(), BoolError>;
+    /// Sets multiple properties at once.
+    ///
+    /// Property change notifications are frozen while setting the properties, so
+    /// every changed property is notified only once afterwards. Stops at the first
+    /// property that can't be set and returns its error.
+    fn set_properties(&self, properties: &[(&str, &dyn ToValue)]) -> Result<(), BoolError>;
     fn get_property<'a, N: Into<&'a str>>(&self, property_name: N) -> Result<Value, BoolError>;
+    /// Sets the property `property_name` from its string representation `value`.
+    ///
+    /// The string is parsed according to the type of the property. Booleans accept
+    /// `true`/`false`, `yes`/`no` and `1`/`0`, enums the name or nick of a value and
+    /// flags a `|` separated list of names or nicks. Other types are converted with the
+    /// transform functions registered for converting from strings, if any.
+    fn set_property_from_str<'a, N: Into<&'a str>>(
+        &self,
+        property_name: N,
+        value: &str,
+    ) -> Result<(), BoolError>;
+    /// Gets the property `property_name` and converts it to `V`.
+    ///
+    /// Returns an error if the property does not exist, is not readable, is
+    /// not of type `V` or its value is `None`. The returned error tells which
+    /// kind of failure happened.
+    fn try_property<V: for<'b> FromValueOptional<'b> + 'static>(
+        &self,
+        property_name: &str,
+    ) -> Result<V, PropertyError>;
+    /// Gets the property `property_name` and converts it to `V`.
+    ///
+    /// # Panics
+    ///
+    /// If the property does not exist, is not readable, is not of type `V` or
+    /// its value is `None`.
+    fn property<V: for<'b> FromValueOptional<'b> + 'static>(&self, property_name: &str) -> V;
     fn has_property<'a, N: Into<&'a str>>(
         &self,
         property_name: N,
@@ -1277,10 +1556,21 @@ pub trait ObjectExt: ObjectType {
     fn get_property_type<'a, N: Into<&'a str>>(&self, property_name: N) -> Option<Type>;
     fn find_property<'a, N: Into<&'a str>>(&self, property_name: N) -> Option<::ParamSpec>;
     fn list_properties(&self) -> Vec<::ParamSpec>;
+    /// Returns a snapshot of the values of all readable properties.
+    ///
+    /// Properties that are not readable are skipped. This is mostly useful
+    /// for debugging and testing.
+    fn properties_hash(&self) -> HashMap<String, Value>;
+    /// Returns information about all signals of the object's type, including the signals
+    /// declared by its parent types.
+    ///
+    /// The signals of the object's own type come first, followed by those of its parents.
+    fn list_all_signals(&self) -> Vec<::SignalInfo>;
 
     fn block_signal(&self, handler_id: &SignalHandlerId);
     fn unblock_signal(&self, handler_id: &SignalHandlerId);
     fn stop_signal_emission(&self, signal_name: &str);
+    fn stop_signal_emission_by_id(&self, signal_id: u32, detail: Option<Quark>);
 
     fn connect<'a, N, F>(
         &self,
@@ -1291,6 +1581,12 @@ pub trait ObjectExt: ObjectType {
     where
         N: Into<&'a str>,
         F: Fn(&[Value]) -> Option<Value> + Send + Sync + 'static;
+    /// Same as [`connect`] but allows closures that are not `Send` and `Sync`.
+    ///
+    /// The closure panics if the signal is emitted from a different thread than
+    /// the one it was connected on.
+    ///
+    /// [`connect`]: #tymethod.connect
     fn connect_local<'a, N, F>(
         &self,
         signal_name: N,
@@ -1309,11 +1605,51 @@ pub trait ObjectExt: ObjectType {
     where
         N: Into<&'a str>,
         F: Fn(&[Value]) -> Option<Value>;
+    /// Connects the given `closure` to the signal.
+    ///
+    /// Different to [`connect`] the closure is connected as is, which allows
+    /// connecting the same closure to multiple signals. No checks of the
+    /// return value of the closure are done.
+    ///
+    /// [`connect`]: #tymethod.connect
+    fn connect_closure<'a, N: Into<&'a str>>(
+        &self,
+        signal_name: N,
+        after: bool,
+        closure: &Closure,
+    ) -> Result<SignalHandlerId, BoolError>;
+    /// Same as [`connect_closure`] but takes `ConnectFlags` instead of `after`.
+    ///
+    /// `ConnectFlags::SWAPPED` is not supported for closures, as there is no
+    /// user data to swap the instance with, and results in an error.
+    ///
+    /// [`connect_closure`]: #tymethod.connect_closure
+    fn connect_with_flags<'a, N: Into<&'a str>>(
+        &self,
+        signal_name: N,
+        flags: ::ConnectFlags,
+        closure: &Closure,
+    ) -> Result<SignalHandlerId, BoolError>;
+    /// Emits the signal `signal_name` with the given arguments.
+    ///
+    /// The number and types of `args` are checked against the signal's
+    /// parameters. The arguments are passed as an array of `GValue`s via
+    /// `g_signal_emitv()`, so no C varargs are involved.
     fn emit<'a, N: Into<&'a str>>(
         &self,
         signal_name: N,
         args: &[&dyn ToValue],
     ) -> Result<Option<Value>, BoolError>;
+    /// Same as [`emit`] but takes the arguments as `Value`s and returns a
+    /// detailed [`EmitError`] on failure.
+    ///
+    /// [`emit`]: #tymethod.emit
+    /// [`EmitError`]: enum.EmitError.html
+    fn try_emit<'a, N: Into<&'a str>>(
+        &self,
+        signal_name: N,
+        args: &[Value],
+    ) -> Result<Option<Value>, ::EmitError>;
     fn disconnect(&self, handler_id: SignalHandlerId);
 
     fn connect_notify<F: Fn(&Self, &::ParamSpec) + Send + Sync + 'static>(
@@ -1321,14 +1657,42 @@ pub trait ObjectExt: ObjectType {
         name: Option<&str>,
         f: F,
     ) -> SignalHandlerId;
+    /// Same as `connect_notify()` but doesn't require `F` to be `Send` or `Sync`.
+    ///
+    /// Panics if the notification is emitted or the closure is dropped on a
+    /// different thread than the one it was connected from.
+    fn connect_notify_local<F: Fn(&Self, &::ParamSpec) + 'static>(
+        &self,
+        name: Option<&str>,
+        f: F,
+    ) -> SignalHandlerId;
     unsafe fn connect_notify_unsafe<F: Fn(&Self, &::ParamSpec)>(
         &self,
         name: Option<&str>,
         f: F,
     ) -> SignalHandlerId;
+    /// Same as `connect_notify()` for a single property, but passes the object to `f` as `U`.
+    fn connect_notify_static<U: IsA<Object>, F: Fn(&U, &::ParamSpec) + Send + Sync + 'static>(
+        &self,
+        property_name: &str,
+        f: F,
+    ) -> SignalHandlerId
+    where
+        Self: IsA<U>;
     fn notify<'a, N: Into<&'a str>>(&self, property_name: N);
     fn notify_by_pspec(&self, pspec: &::ParamSpec);
 
+    /// Freezes property change notifications until the returned guard is dropped.
+    ///
+    /// Notifications emitted in the meantime are queued and emitted once per property when the
+    /// last guard is dropped.
+    fn freeze_notify(&self) -> FreezeNotifyGuard;
+
+    /// Calls `f` with property change notifications frozen.
+    ///
+    /// The queued notifications are emitted after `f` returns, or when it panics.
+    fn with_frozen_notify<F: FnOnce(&Self) -> R, R>(&self, f: F) -> R;
+
     fn downgrade(&self) -> WeakRef<Self>;
 
     fn bind_property<'a, O: ObjectType, N: Into<&'a str>, M: Into<&'a str>>(
@@ -1338,7 +1702,42 @@ pub trait ObjectExt: ObjectType {
         target_property: M,
     ) -> BindingBuilder<'a>;
 
+    /// Returns the current strong reference count of the object.
+    ///
+    /// This is only meant for debugging and testing, e.g. to check for leaked
+    /// references. Other threads can change the reference count at any time, so
+    /// code should never depend on its value.
     fn ref_count(&self) -> u32;
+
+    /// Sets the user data `value` for the given `key` on the object.
+    ///
+    /// Any previously set user data for the same key is dropped.
+    ///
+    /// This is unsafe because the type of the value is not checked when
+    /// retrieving it again via [`get_qdata`] or [`steal_qdata`].
+    ///
+    /// [`get_qdata`]: #tymethod.get_qdata
+    /// [`steal_qdata`]: #tymethod.steal_qdata
+    unsafe fn set_qdata<QD: 'static>(&self, key: Quark, value: QD);
+
+    /// Returns a reference to the user data set for the given `key`, if any.
+    ///
+    /// This is unsafe because it must be ensured that `QD` is the same type
+    /// that was set via [`set_qdata`].
+    ///
+    /// [`set_qdata`]: #tymethod.set_qdata
+    unsafe fn get_qdata<QD: 'static>(&self, key: Quark) -> Option<&QD>;
+
+    /// Removes the user data set for the given `key` and returns it, if any.
+    ///
+    /// Different to setting new user data, the old value is not dropped but
+    /// ownership is transferred to the caller.
+    ///
+    /// This is unsafe because it must be ensured that `QD` is the same type
+    /// that was set via [`set_qdata`].
+    ///
+    /// [`set_qdata`]: #tymethod.set_qdata
+    unsafe fn steal_qdata<QD: 'static>(&self, key: Quark) -> Option<QD>;
 }
 
 impl<T: ObjectType> ObjectExt for T {
@@ -1444,6 +1843,24 @@ impl<T: ObjectType> ObjectExt for T {
         Ok(())
     }
 
+    fn set_property_from_str<'a, N: Into<&'a str>>(
+        &self,
+        property_name: N,
+        value: &str,
+    ) -> Result<(), BoolError> {
+        let property_name = property_name.into();
+
+        let pspec = match self.find_property(property_name) {
+            Some(pspec) => pspec,
+            None => {
+                return Err(glib_bool_error!("property not found"));
+            }
+        };
+
+        let value = value_from_str(pspec.get_value_type(), value)?;
+        self.set_property(property_name, &value)
+    }
+
     fn get_property<'a, N: Into<&'a str>>(&self, property_name: N) -> Result<Value, BoolError> {
         let property_name = property_name.into();
 
@@ -1475,6 +1892,41 @@ impl<T: ObjectType> ObjectExt for T {
         }
     }
 
+    fn try_property<V: for<'b> FromValueOptional<'b> + 'static>(
+        &self,
+        property_name: &str,
+    ) -> Result<V, PropertyError> {
+        let pspec = match self.find_property(property_name) {
+            Some(pspec) => pspec,
+            None => return Err(PropertyError::NotFound),
+        };
+
+        if !pspec.get_flags().contains(::ParamFlags::READABLE) {
+            return Err(PropertyError::NotReadable);
+        }
+
+        let value = self
+            .get_property(property_name)
+            .map_err(PropertyError::Failed)?;
+        match value.get::<V>() {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Err(PropertyError::Failed(glib_bool_error!(
+                "Property value is None"
+            ))),
+            Err(err) => Err(PropertyError::TypeMismatch {
+                expected: err.requested,
+                actual: err.actual,
+            }),
+        }
+    }
+
+    fn property<V: for<'b> FromValueOptional<'b> + 'static>(&self, property_name: &str) -> V {
+        match self.try_property(property_name) {
+            Ok(value) => value,
+            Err(err) => panic!("Failed to get property '{}': {}", property_name, err),
+        }
+    }
+
     fn block_signal(&self, handler_id: &SignalHandlerId) {
         unsafe {
             gobject_sys::g_signal_handler_block(
@@ -1502,6 +1954,16 @@ impl<T: ObjectType> ObjectExt for T {
         }
     }
 
+    fn stop_signal_emission_by_id(&self, signal_id: u32, detail: Option<Quark>) {
+        unsafe {
+            gobject_sys::g_signal_stop_emission(
+                self.as_object_ref().to_glib_none().0,
+                signal_id,
+                detail.map(|d| d.to_glib()).unwrap_or(0),
+            );
+        }
+    }
+
     fn disconnect(&self, handler_id: SignalHandlerId) {
         unsafe {
             gobject_sys::g_signal_handler_disconnect(
@@ -1519,6 +1981,16 @@ impl<T: ObjectType> ObjectExt for T {
         unsafe { self.connect_notify_unsafe(name, f) }
     }
 
+    fn connect_notify_local<F: Fn(&Self, &::ParamSpec) + 'static>(
+        &self,
+        name: Option<&str>,
+        f: F,
+    ) -> SignalHandlerId {
+        let f = crate::ThreadGuard::new(f);
+
+        unsafe { self.connect_notify_unsafe(name, move |obj, pspec| (f.get_ref())(obj, pspec)) }
+    }
+
     unsafe fn connect_notify_unsafe<F: Fn(&Self, &::ParamSpec)>(
         &self,
         name: Option<&str>,
@@ -1553,6 +2025,19 @@ impl<T: ObjectType> ObjectExt for T {
         )
     }
 
+    fn connect_notify_static<U: IsA<Object>, F: Fn(&U, &::ParamSpec) + Send + Sync + 'static>(
+        &self,
+        property_name: &str,
+        f: F,
+    ) -> SignalHandlerId
+    where
+        Self: IsA<U>,
+    {
+        self.connect_notify(Some(property_name), move |obj, pspec| {
+            f(obj.upcast_ref::<U>(), pspec)
+        })
+    }
+
     fn notify<'a, N: Into<&'a str>>(&self, property_name: N) {
         let property_name = property_name.into();
 
@@ -1573,6 +2058,28 @@ impl<T: ObjectType> ObjectExt for T {
         }
     }
 
+    fn set_properties(&self, properties: &[(&str, &dyn ToValue)]) -> Result<(), BoolError> {
+        let _guard = self.freeze_notify();
+        for &(name, value) in properties {
+            self.set_property(name, value)?;
+        }
+
+        Ok(())
+    }
+
+    fn freeze_notify(&self) -> FreezeNotifyGuard {
+        unsafe {
+            let ptr = self.as_object_ref().to_glib_none().0;
+            gobject_sys::g_object_freeze_notify(ptr);
+            FreezeNotifyGuard(from_glib_none(ptr))
+        }
+    }
+
+    fn with_frozen_notify<F: FnOnce(&Self) -> R, R>(&self, f: F) -> R {
+        let _guard = self.freeze_notify();
+        f(self)
+    }
+
     fn has_property<'a, N: Into<&'a str>>(
         &self,
         property_name: N,
@@ -1593,6 +2100,44 @@ impl<T: ObjectType> ObjectExt for T {
         self.get_object_class().list_properties()
     }
 
+    fn properties_hash(&self) -> HashMap<String, Value> {
+        self.list_properties()
+            .into_iter()
+            .filter(|pspec| pspec.get_flags().contains(::ParamFlags::READABLE))
+            .filter_map(|pspec| {
+                let name = pspec.get_name();
+                self.get_property(name.as_str())
+                    .ok()
+                    .map(|value| (name, value))
+            })
+            .collect()
+    }
+
+    fn list_all_signals(&self) -> Vec<::SignalInfo> {
+        let mut signals = Vec::new();
+        let mut seen = HashSet::new();
+
+        let mut type_ = Some(self.get_type());
+        while let Some(t) = type_ {
+            unsafe {
+                let mut n_ids = 0;
+                let ids = gobject_sys::g_signal_list_ids(t.to_glib(), &mut n_ids);
+                if !ids.is_null() {
+                    for i in 0..n_ids as usize {
+                        let id = *ids.add(i);
+                        if seen.insert(id) {
+                            signals.extend(::SignalInfo::query(id));
+                        }
+                    }
+                    glib_sys::g_free(ids as *mut _);
+                }
+            }
+            type_ = t.parent();
+        }
+
+        signals
+    }
+
     fn connect<'a, N, F>(
         &self,
         signal_name: N,
@@ -1721,36 +2266,48 @@ impl<T: ObjectType> ObjectExt for T {
                 }
             }
         });
-        let handler = gobject_sys::g_signal_connect_closure_by_id(
-            self.as_object_ref().to_glib_none().0,
-            signal_id,
-            signal_detail,
-            closure.to_glib_none().0,
-            after.to_glib(),
-        );
 
-        if handler == 0 {
-            Err(glib_bool_error!("Failed to connect to signal"))
+        let flags = if after {
+            ::ConnectFlags::AFTER
         } else {
-            Ok(from_glib(handler))
-        }
+            ::ConnectFlags::empty()
+        };
+        self.connect_with_flags(signal_name, flags, &closure)
     }
 
-    fn emit<'a, N: Into<&'a str>>(
+    fn connect_closure<'a, N: Into<&'a str>>(
         &self,
         signal_name: N,
-        args: &[&dyn ToValue],
-    ) -> Result<Option<Value>, BoolError> {
+        after: bool,
+        closure: &Closure,
+    ) -> Result<SignalHandlerId, BoolError> {
+        let flags = if after {
+            ::ConnectFlags::AFTER
+        } else {
+            ::ConnectFlags::empty()
+        };
+        self.connect_with_flags(signal_name, flags, closure)
+    }
+
+    fn connect_with_flags<'a, N: Into<&'a str>>(
+        &self,
+        signal_name: N,
+        flags: ::ConnectFlags,
+        closure: &Closure,
+    ) -> Result<SignalHandlerId, BoolError> {
         let signal_name: &str = signal_name.into();
-        unsafe {
-            let type_ = self.get_type();
 
+        if flags.contains(::ConnectFlags::SWAPPED) {
+            return Err(glib_bool_error!("Can't connect swapped closures"));
+        }
+
+        unsafe {
             let mut signal_id = 0;
             let mut signal_detail = 0;
 
             let found: bool = from_glib(gobject_sys::g_signal_parse_name(
                 signal_name.to_glib_none().0,
-                type_.to_glib(),
+                self.get_type().to_glib(),
                 &mut signal_id,
                 &mut signal_detail,
                 true.to_glib(),
@@ -1760,71 +2317,42 @@ impl<T: ObjectType> ObjectExt for T {
                 return Err(glib_bool_error!("Signal not found"));
             }
 
-            let mut details = mem::MaybeUninit::zeroed();
-            gobject_sys::g_signal_query(signal_id, details.as_mut_ptr());
-            let details = details.assume_init();
-            if details.signal_id != signal_id {
-                return Err(glib_bool_error!("Signal not found"));
-            }
-
-            if details.n_params != args.len() as u32 {
-                return Err(glib_bool_error!("Incompatible number of arguments"));
-            }
-
-            for (i, item) in args.iter().enumerate() {
-                let arg_type =
-                    *(details.param_types.add(i)) & (!gobject_sys::G_TYPE_FLAG_RESERVED_ID_BIT);
-                if arg_type != item.to_value_type().to_glib() {
-                    return Err(glib_bool_error!("Incompatible argument types"));
-                }
-            }
-
-            let mut v_args: Vec<Value>;
-            let mut s_args: [Value; 10] = mem::zeroed();
-            let self_v = {
-                let mut v = Value::uninitialized();
-                gobject_sys::g_value_init(v.to_glib_none_mut().0, self.get_type().to_glib());
-                gobject_sys::g_value_set_object(
-                    v.to_glib_none_mut().0,
-                    self.as_object_ref().to_glib_none().0,
-                );
-                v
-            };
-            let args = if args.len() < 10 {
-                s_args[0] = self_v;
-                for (i, arg) in args.iter().enumerate() {
-                    s_args[i + 1] = arg.to_value();
-                }
-                &s_args[0..=args.len()]
-            } else {
-                v_args = Vec::with_capacity(args.len() + 1);
-                v_args.push(self_v);
-                for arg in args {
-                    v_args.push(arg.to_value());
-                }
-                v_args.as_slice()
-            };
-
-            let mut return_value = Value::uninitialized();
-            if details.return_type != gobject_sys::G_TYPE_NONE {
-                gobject_sys::g_value_init(return_value.to_glib_none_mut().0, details.return_type);
-            }
-
-            gobject_sys::g_signal_emitv(
-                mut_override(args.as_ptr()) as *mut gobject_sys::GValue,
+            let handler = gobject_sys::g_signal_connect_closure_by_id(
+                self.as_object_ref().to_glib_none().0,
                 signal_id,
                 signal_detail,
-                return_value.to_glib_none_mut().0,
+                closure.to_glib_none().0,
+                flags.contains(::ConnectFlags::AFTER).to_glib(),
             );
 
-            if return_value.type_() != Type::Unit && return_value.type_() != Type::Invalid {
-                Ok(Some(return_value))
+            if handler == 0 {
+                Err(glib_bool_error!("Failed to connect to signal"))
             } else {
-                Ok(None)
+                Ok(from_glib(handler))
             }
         }
     }
 
+    fn emit<'a, N: Into<&'a str>>(
+        &self,
+        signal_name: N,
+        args: &[&dyn ToValue],
+    ) -> Result<Option<Value>, BoolError> {
+        unsafe {
+            emit_internal(self.as_object_ref(), self.get_type(), signal_name.into(), args)
+                .map_err(|err| glib_bool_error!("{}", err))
+        }
+    }
+
+    fn try_emit<'a, N: Into<&'a str>>(
+        &self,
+        signal_name: N,
+        args: &[Value],
+    ) -> Result<Option<Value>, ::EmitError> {
+        let args = args.iter().map(|arg| arg as &dyn ToValue).collect::<Vec<_>>();
+        unsafe { emit_internal(self.as_object_ref(), self.get_type(), signal_name.into(), &args) }
+    }
+
     fn downgrade(&self) -> WeakRef<T> {
         unsafe {
             let w = WeakRef(Box::new(mem::zeroed()), PhantomData);
@@ -1854,6 +2382,43 @@ impl<T: ObjectType> ObjectExt for T {
 
         unsafe { glib_sys::g_atomic_int_get(&(*ptr).ref_count as *const u32 as *const i32) as u32 }
     }
+
+    unsafe fn set_qdata<QD: 'static>(&self, key: Quark, value: QD) {
+        unsafe extern "C" fn drop_value<QD>(ptr: glib_sys::gpointer) {
+            debug_assert!(!ptr.is_null());
+            let value: Box<QD> = Box::from_raw(ptr as *mut QD);
+            drop(value)
+        }
+
+        let ptr = Box::into_raw(Box::new(value)) as glib_sys::gpointer;
+        gobject_sys::g_object_set_qdata_full(
+            self.as_object_ref().to_glib_none().0,
+            key.to_glib(),
+            ptr,
+            Some(drop_value::<QD>),
+        );
+    }
+
+    unsafe fn get_qdata<QD: 'static>(&self, key: Quark) -> Option<&QD> {
+        let ptr =
+            gobject_sys::g_object_get_qdata(self.as_object_ref().to_glib_none().0, key.to_glib());
+        if ptr.is_null() {
+            None
+        } else {
+            Some(&*(ptr as *const QD))
+        }
+    }
+
+    unsafe fn steal_qdata<QD: 'static>(&self, key: Quark) -> Option<QD> {
+        let ptr =
+            gobject_sys::g_object_steal_qdata(self.as_object_ref().to_glib_none().0, key.to_glib());
+        if ptr.is_null() {
+            None
+        } else {
+            let value: Box<QD> = Box::from_raw(ptr as *mut QD);
+            Some(*value)
+        }
+    }
 }
 
 impl ObjectClass {
@@ -1908,6 +2473,164 @@ impl ObjectClass {
     }
 }
 
+/// A reference to the default vtable of an interface type.
+///
+/// This allows inspecting the properties an interface declares without having an instance
+/// of a type implementing it.
+#[derive(Debug)]
+pub struct InterfaceRef {
+    ptr: ptr::NonNull<gobject_sys::GTypeInterface>,
+    owned: bool,
+}
+
+impl InterfaceRef {
+    /// Returns the default vtable of the interface `type_` if it was already initialized.
+    ///
+    /// This is the case once it was referenced or a type implementing the interface was
+    /// instantiated.
+    pub fn peek(type_: Type) -> Option<InterfaceRef> {
+        if !type_.is_a(&Type::BaseInterface) {
+            return None;
+        }
+
+        unsafe {
+            let ptr = gobject_sys::g_type_default_interface_peek(type_.to_glib());
+            ptr::NonNull::new(ptr as *mut gobject_sys::GTypeInterface)
+                .map(|ptr| InterfaceRef { ptr, owned: false })
+        }
+    }
+
+    /// Returns the default vtable of the interface `type_`, initializing it if necessary.
+    pub fn new(type_: Type) -> Option<InterfaceRef> {
+        if !type_.is_a(&Type::BaseInterface) {
+            return None;
+        }
+
+        unsafe {
+            let ptr = gobject_sys::g_type_default_interface_ref(type_.to_glib());
+            ptr::NonNull::new(ptr as *mut gobject_sys::GTypeInterface)
+                .map(|ptr| InterfaceRef { ptr, owned: true })
+        }
+    }
+
+    /// Returns the interface type.
+    pub fn get_type(&self) -> Type {
+        unsafe { from_glib(self.ptr.as_ref().g_type) }
+    }
+
+    pub fn find_property(&self, property_name: &str) -> Option<::ParamSpec> {
+        unsafe {
+            from_glib_none(gobject_sys::g_object_interface_find_property(
+                self.ptr.as_ptr() as *mut _,
+                property_name.to_glib_none().0,
+            ))
+        }
+    }
+
+    pub fn list_properties(&self) -> Vec<::ParamSpec> {
+        unsafe {
+            let mut n_properties = 0;
+
+            let props = gobject_sys::g_object_interface_list_properties(
+                self.ptr.as_ptr() as *mut _,
+                &mut n_properties,
+            );
+            FromGlibContainer::from_glib_container_num(props, n_properties as usize)
+        }
+    }
+}
+
+impl Drop for InterfaceRef {
+    fn drop(&mut self) {
+        if self.owned {
+            unsafe {
+                gobject_sys::g_type_default_interface_unref(self.ptr.as_ptr() as *mut _);
+            }
+        }
+    }
+}
+
+/// An error returned by
+/// [`ObjectExt::try_property`](trait.ObjectExt.html#tymethod.try_property).
+#[derive(Debug)]
+pub enum PropertyError {
+    /// The object has no property with the given name.
+    NotFound,
+    /// The property is not readable.
+    NotReadable,
+    /// The value of the property can't be converted to the requested type.
+    TypeMismatch { expected: Type, actual: Type },
+    /// Getting the property value failed.
+    Failed(BoolError),
+}
+
+impl fmt::Display for PropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PropertyError::NotFound => f.write_str("property not found"),
+            PropertyError::NotReadable => f.write_str("property is not readable"),
+            PropertyError::TypeMismatch { expected, actual } => write!(
+                f,
+                "property type mismatch: expected {}, actual {}",
+                expected, actual
+            ),
+            PropertyError::Failed(ref err) => write!(f, "failed to get property: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for PropertyError {}
+
+/// A guard that thaws property change notifications of an object when dropped.
+///
+/// Returned by [`ObjectExt::freeze_notify`](trait.ObjectExt.html#tymethod.freeze_notify).
+#[derive(Debug)]
+pub struct FreezeNotifyGuard(Object);
+
+impl Drop for FreezeNotifyGuard {
+    fn drop(&mut self) {
+        unsafe {
+            gobject_sys::g_object_thaw_notify(self.0.to_glib_none().0);
+        }
+    }
+}
+
+/// An object whose runtime type is known to be exactly `T`.
+///
+/// This dereferences to `T`, so it can be passed to anything expecting a `T` or one of its
+/// superclasses without further casts.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct TypedObject<T: IsA<Object>>(T);
+
+impl<T: IsA<Object>> TypedObject<T> {
+    /// Wraps `obj` if its runtime type is exactly `T`, otherwise returns it again.
+    pub fn new<O: IsA<Object>>(obj: O) -> Result<Self, O> {
+        if obj.get_type() == T::static_type() {
+            Ok(TypedObject(unsafe { obj.unsafe_cast() }))
+        } else {
+            Err(obj)
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: IsA<Object>> ops::Deref for TypedObject<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: IsA<Object>> AsRef<T> for TypedObject<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
 glib_wrapper! {
     pub struct InitiallyUnowned(Object<gobject_sys::GInitiallyUnowned, gobject_sys::GInitiallyUnownedClass, InitiallyUnownedClass>);
 
@@ -1916,6 +2639,15 @@ glib_wrapper! {
     }
 }
 
+/// A weak reference to an object.
+///
+/// Upgrading is atomic via `g_weak_ref_get()`: it either returns a new strong
+/// reference or `None` if the object was already finalized. If the object type
+/// is `Send + Sync` the weak reference can be shared with and upgraded from
+/// other threads, otherwise [`SendWeakRef`] can be used to move it between
+/// threads.
+///
+/// [`SendWeakRef`]: struct.SendWeakRef.html
 pub struct WeakRef<T: ObjectType>(Box<gobject_sys::GWeakRef>, PhantomData<*const T>);
 
 impl<T: ObjectType> WeakRef<T> {
@@ -1970,7 +2702,7 @@ impl<T: ObjectType> Default for WeakRef<T> {
     }
 }
 
-unsafe impl<T: ObjectType + Sync + Sync> Sync for WeakRef<T> {}
+unsafe impl<T: ObjectType + Send + Sync> Sync for WeakRef<T> {}
 unsafe impl<T: ObjectType + Send + Sync> Send for WeakRef<T> {}
 
 /// A weak reference to the object it was created for that can be sent to
@@ -0,0 +1,78 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use gobject_sys;
+use std::error;
+use std::fmt;
+use translate::*;
+use BoolError;
+use Object;
+use StaticType;
+use ToValue;
+use Type;
+
+/// Error returned by [`ObjectFactory::create`](struct.ObjectFactory.html#method.create).
+#[derive(Debug, Clone)]
+pub enum FactoryError {
+    /// No type with the given name is registered.
+    UnknownType(String),
+    /// The type is not a `GObject` type.
+    NotAnObjectType(Type),
+    /// The type is abstract and can't be instantiated.
+    AbstractType(Type),
+    /// Setting the construction properties failed.
+    PropertyError(BoolError),
+}
+
+impl fmt::Display for FactoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FactoryError::UnknownType(ref name) => write!(f, "Unknown type '{}'", name),
+            FactoryError::NotAnObjectType(type_) => {
+                write!(f, "Type '{}' is not an object type", type_)
+            }
+            FactoryError::AbstractType(type_) => write!(f, "Type '{}' is abstract", type_),
+            FactoryError::PropertyError(ref err) => write!(f, "Failed to create object: {}", err),
+        }
+    }
+}
+
+impl error::Error for FactoryError {}
+
+/// Creates objects by their type name, e.g. for plugin systems.
+///
+/// The types have to be registered with the type system already, which
+/// happens for subclasses on the first call to `get_type()`.
+#[derive(Debug, Clone, Copy)]
+pub struct ObjectFactory;
+
+impl ObjectFactory {
+    /// Creates a new instance of the type named `type_name` with the given construction
+    /// properties.
+    pub fn create(
+        type_name: &str,
+        properties: &[(&str, &dyn ToValue)],
+    ) -> Result<Object, FactoryError> {
+        let type_ = match Type::from_name(type_name) {
+            Some(type_) => type_,
+            None => return Err(FactoryError::UnknownType(type_name.to_string())),
+        };
+
+        if !type_.is_a(&Object::static_type()) {
+            return Err(FactoryError::NotAnObjectType(type_));
+        }
+
+        let is_abstract: bool = unsafe {
+            from_glib(gobject_sys::g_type_test_flags(
+                type_.to_glib(),
+                gobject_sys::G_TYPE_FLAG_ABSTRACT,
+            ))
+        };
+        if is_abstract {
+            return Err(FactoryError::AbstractType(type_));
+        }
+
+        Object::new(type_, properties).map_err(FactoryError::PropertyError)
+    }
+}
@@ -49,6 +49,17 @@ impl ParamSpec {
         }
     }
 
+    /// Returns the default value of the param spec without copying it.
+    ///
+    /// The value is owned by the param spec and stays valid as long as it.
+    pub fn get_default_value_ref(&self) -> &Value {
+        unsafe {
+            let value = gobject_sys::g_param_spec_get_default_value(self.to_glib_none().0);
+            assert!(!value.is_null());
+            &*(value as *const Value)
+        }
+    }
+
     pub fn get_name(&self) -> String {
         unsafe { from_glib_none(gobject_sys::g_param_spec_get_name(self.to_glib_none().0)) }
     }
@@ -535,3 +546,18 @@ impl ParamSpec {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_value() {
+        let pspec = ParamSpec::int("count", "Count", "Count", 0, 100, 42, ParamFlags::READWRITE);
+        assert_eq!(pspec.get_default_value_ref().get_some::<i32>(), Ok(42));
+        assert_eq!(pspec.get_default_value().unwrap().get_some::<i32>(), Ok(42));
+
+        let pspec = ParamSpec::string("name", "Name", "Name", None, ParamFlags::READWRITE);
+        assert_eq!(pspec.get_default_value_ref().get::<&str>(), Ok(None));
+    }
+}
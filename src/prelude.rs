@@ -2,5 +2,5 @@
 
 pub use {
     Cast, Continue, IsA, IsClassFor, ObjectExt, ObjectType, StaticType, StaticVariantType,
-    ToSendValue, ToValue, ToVariant,
+    ToSendValue, ToValue, ToVariant, TypeModuleExt,
 };
@@ -8,14 +8,31 @@ use glib_sys::{gboolean, gpointer};
 use gobject_sys::{self, GCallback};
 use libc::{c_char, c_ulong, c_void};
 use object::ObjectType;
+use std::error;
+use std::ffi::CStr;
+use std::fmt;
 use std::mem;
 use std::num::NonZeroU64;
 use translate::{from_glib, FromGlib, ToGlib, ToGlibPtr};
+use SignalFlags;
+use Type;
 
 /// The id of a signal that is returned by `connect`.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub struct SignalHandlerId(NonZeroU64);
 
+impl SignalHandlerId {
+    /// Returns `true` if the handler with this id is connected to `instance`.
+    pub fn is_connected<T: ObjectType>(&self, instance: &T) -> bool {
+        unsafe {
+            from_glib(gobject_sys::g_signal_handler_is_connected(
+                instance.as_object_ref().to_glib_none().0,
+                self.to_glib(),
+            ))
+        }
+    }
+}
+
 impl ToGlib for SignalHandlerId {
     type GlibType = c_ulong;
 
@@ -33,6 +50,121 @@ impl FromGlib<c_ulong> for SignalHandlerId {
     }
 }
 
+/// Error returned by [`ObjectExt::try_emit`](trait.ObjectExt.html#tymethod.try_emit).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EmitError {
+    /// The object has no signal with the given name.
+    UnknownSignal,
+    /// The number of arguments does not match the signal's parameters.
+    ArgumentCountMismatch { expected: usize, got: usize },
+    /// The argument at `index` has a different type than the signal's parameter.
+    ArgumentTypeMismatch {
+        index: usize,
+        expected: Type,
+        got: Type,
+    },
+    /// The signal has a return type that can't be stored in a `Value`, or the
+    /// emission returned a value of a different type.
+    InvalidReturnType,
+}
+
+impl fmt::Display for EmitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EmitError::UnknownSignal => f.write_str("Signal not found"),
+            EmitError::ArgumentCountMismatch { expected, got } => write!(
+                f,
+                "Incompatible number of arguments: expected {}, got {}",
+                expected, got
+            ),
+            EmitError::ArgumentTypeMismatch {
+                index,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Incompatible argument types: argument {} expected {}, got {}",
+                index, expected, got
+            ),
+            EmitError::InvalidReturnType => f.write_str("Invalid return type"),
+        }
+    }
+}
+
+impl error::Error for EmitError {}
+
+/// Information about a signal as returned by `g_signal_query()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignalInfo {
+    signal_id: u32,
+    name: String,
+    itype: Type,
+    flags: SignalFlags,
+    return_type: Type,
+    param_types: Vec<Type>,
+}
+
+impl SignalInfo {
+    /// Queries the information of the signal with id `signal_id`.
+    pub fn query(signal_id: u32) -> Option<SignalInfo> {
+        unsafe {
+            let mut details = mem::MaybeUninit::zeroed();
+            gobject_sys::g_signal_query(signal_id, details.as_mut_ptr());
+            let details = details.assume_init();
+            if details.signal_id == 0 || details.signal_id != signal_id {
+                return None;
+            }
+
+            // The G_SIGNAL_TYPE_STATIC_SCOPE flag is not part of the types
+            let param_types = (0..details.n_params as usize)
+                .map(|i| {
+                    from_glib(
+                        *details.param_types.add(i) & !gobject_sys::G_TYPE_FLAG_RESERVED_ID_BIT,
+                    )
+                })
+                .collect();
+
+            Some(SignalInfo {
+                signal_id,
+                name: CStr::from_ptr(details.signal_name)
+                    .to_string_lossy()
+                    .into_owned(),
+                itype: from_glib(details.itype),
+                flags: from_glib(details.signal_flags),
+                return_type: from_glib(
+                    details.return_type & !gobject_sys::G_TYPE_FLAG_RESERVED_ID_BIT,
+                ),
+                param_types,
+            })
+        }
+    }
+
+    pub fn get_signal_id(&self) -> u32 {
+        self.signal_id
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type that declared the signal.
+    pub fn get_itype(&self) -> Type {
+        self.itype
+    }
+
+    pub fn get_flags(&self) -> SignalFlags {
+        self.flags
+    }
+
+    pub fn get_return_type(&self) -> Type {
+        self.return_type
+    }
+
+    pub fn get_param_types(&self) -> &[Type] {
+        &self.param_types
+    }
+}
+
 /// Whether to propagate the signal to the default handler.
 ///
 /// Don't inhibit default handlers without a reason, they're usually helpful.
@@ -10,6 +10,7 @@ use std::mem::transmute;
 use std::num::NonZeroU32;
 #[cfg(unix)]
 use std::os::unix::io::RawFd;
+use std::time::Duration;
 use translate::{from_glib, from_glib_full, FromGlib, ToGlib, ToGlibPtr};
 #[cfg(any(unix, feature = "dox"))]
 use IOCondition;
@@ -711,4 +712,76 @@ impl Source {
             )
         }
     }
+
+    /// Sets a name for the source that is used for debugging and profiling.
+    ///
+    /// Prefer passing the name when creating the source, e.g. via [`idle_source_new`].
+    ///
+    /// # Safety
+    ///
+    /// `g_source_set_name()` frees the previous name, which races with
+    /// `get_name()` and with GLib reading the name from other threads, e.g. while
+    /// the source is dispatched by a main context running on another thread. The
+    /// caller has to ensure that the name of the source is not accessed concurrently,
+    /// for example by only calling this before the source is attached.
+    ///
+    /// [`idle_source_new`]: fn.idle_source_new.html
+    pub unsafe fn set_name(&self, name: &str) {
+        glib_sys::g_source_set_name(self.to_glib_none().0, name.to_glib_none().0);
+    }
+
+    /// Sets the monotonic time, as returned by `get_monotonic_time()`, at which the
+    /// source will be dispatched.
+    ///
+    /// A `ready_time` of `-1` means the source is never woken up because of the time,
+    /// `0` means it is dispatched immediately. It is safe to call this from any thread.
+    pub fn set_ready_time(&self, ready_time: i64) {
+        unsafe {
+            glib_sys::g_source_set_ready_time(self.to_glib_none().0, ready_time);
+        }
+    }
+
+    /// Sets the source to be dispatched once `duration` has passed from now.
+    pub fn set_ready_time_in(&self, duration: Duration) {
+        let micros = duration.as_micros();
+        let micros = if micros > i64::max_value() as u128 {
+            i64::max_value()
+        } else {
+            micros as i64
+        };
+        self.set_ready_time(::get_monotonic_time().saturating_add(micros));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_name() {
+        let source = idle_source_new(Some("initial-name"), PRIORITY_DEFAULT, || Continue(false));
+        assert_eq!(source.get_name().unwrap(), "initial-name");
+
+        // The source is not attached and not shared with other threads
+        unsafe {
+            source.set_name("my-poller");
+        }
+        assert_eq!(source.get_name().unwrap(), "my-poller");
+    }
+
+    #[test]
+    fn test_source_ready_time() {
+        let source = idle_source_new(None, PRIORITY_DEFAULT, || Continue(false));
+        assert_eq!(source.get_ready_time(), -1);
+
+        source.set_ready_time(1234);
+        assert_eq!(source.get_ready_time(), 1234);
+
+        let now = ::get_monotonic_time();
+        source.set_ready_time_in(Duration::from_secs(1));
+        assert!(source.get_ready_time() >= now + 1_000_000);
+
+        source.set_ready_time(-1);
+        assert_eq!(source.get_ready_time(), -1);
+    }
 }
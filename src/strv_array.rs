@@ -0,0 +1,152 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use gstring::GString;
+use std::iter::FromIterator;
+use std::mem;
+use std::ops;
+use std::os::raw::c_char;
+use std::ptr;
+use std::slice;
+use std::vec;
+use translate::*;
+
+/// A `NULL`-terminated array of strings, i.e. a `gchar **`.
+#[derive(Debug, Default)]
+pub struct StrvArray(Vec<GString>);
+
+impl StrvArray {
+    /// Creates a new, empty array.
+    pub fn new() -> Self {
+        StrvArray(Vec::new())
+    }
+
+    /// Creates a new array from the strings of `iter`.
+    pub fn collect_from_iter<I, S>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        StrvArray(iter.into_iter().map(|s| GString::from(s.as_ref())).collect())
+    }
+
+    /// Appends `s` to the end of the array.
+    pub fn push<S: AsRef<str>>(&mut self, s: S) {
+        self.0.push(GString::from(s.as_ref()));
+    }
+
+    pub fn into_inner(self) -> Vec<GString> {
+        self.0
+    }
+}
+
+impl ops::Deref for StrvArray {
+    type Target = [GString];
+
+    fn deref(&self) -> &[GString] {
+        &self.0
+    }
+}
+
+impl From<Vec<GString>> for StrvArray {
+    fn from(v: Vec<GString>) -> Self {
+        StrvArray(v)
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for StrvArray {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        StrvArray::collect_from_iter(iter)
+    }
+}
+
+impl IntoIterator for StrvArray {
+    type Item = GString;
+    type IntoIter = vec::IntoIter<GString>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a StrvArray {
+    type Item = &'a GString;
+    type IntoIter = slice::Iter<'a, GString>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl GlibPtrDefault for StrvArray {
+    type GlibType = *mut *mut c_char;
+}
+
+impl<'a> ToGlibPtr<'a, *mut *mut c_char> for StrvArray {
+    type Storage = Vec<*mut c_char>;
+
+    #[inline]
+    fn to_glib_none(&'a self) -> Stash<'a, *mut *mut c_char, Self> {
+        let mut v: Vec<_> = self.0.iter().map(|s| s.as_ptr() as *mut c_char).collect();
+        v.push(ptr::null_mut());
+
+        Stash(v.as_mut_ptr(), v)
+    }
+
+    #[inline]
+    fn to_glib_full(&self) -> *mut *mut c_char {
+        unsafe {
+            let v_ptr = glib_sys::g_malloc0(mem::size_of::<*mut c_char>() * (self.0.len() + 1))
+                as *mut *mut c_char;
+
+            for (i, s) in self.0.iter().enumerate() {
+                ptr::write(v_ptr.add(i), s.to_glib_full());
+            }
+
+            v_ptr
+        }
+    }
+}
+
+impl FromGlibPtrNone<*mut *mut c_char> for StrvArray {
+    #[inline]
+    unsafe fn from_glib_none(ptr: *mut *mut c_char) -> Self {
+        StrvArray(FromGlibPtrArrayContainerAsVec::from_glib_none_as_vec(ptr))
+    }
+}
+
+impl FromGlibPtrFull<*mut *mut c_char> for StrvArray {
+    #[inline]
+    unsafe fn from_glib_full(ptr: *mut *mut c_char) -> Self {
+        let res = from_glib_none(ptr);
+        glib_sys::g_strfreev(ptr);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strv_array() {
+        let strv = StrvArray::collect_from_iter(&["foo", "bar"]);
+        assert_eq!(strv.len(), 2);
+
+        unsafe {
+            let ptr: *mut *mut c_char = strv.to_glib_full();
+            assert_eq!(glib_sys::g_strv_length(ptr), 2);
+
+            let copy: StrvArray = from_glib_full(ptr);
+            let strings: Vec<String> = copy.into_iter().map(String::from).collect();
+            assert_eq!(strings, vec![String::from("foo"), String::from("bar")]);
+        }
+
+        let stash: Stash<*mut *mut c_char, _> = strv.to_glib_none();
+        unsafe {
+            assert_eq!(glib_sys::g_strv_length(stash.0), 2);
+        }
+    }
+}
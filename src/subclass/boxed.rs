@@ -6,10 +6,41 @@
 
 use glib_sys;
 use gobject_sys;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt;
+use std::mem;
 use std::ops;
+use std::sync::{Arc, Mutex};
 use translate::*;
 use value::*;
 
+/// Strategy for copying and freeing the values of a [`BoxedType`].
+///
+/// [`BoxedType`]: trait.BoxedType.html
+pub enum BoxedCopyStrategy<T> {
+    /// Values are stored in a `Box` and copied via `Clone::clone`.
+    ///
+    /// `glib_boxed_type!(copy_strategy: Clone)` fills in the function.
+    Clone(fn(&T) -> T),
+    /// Values are stored in an `Arc` and copying only increases the reference count.
+    ///
+    /// This does not require the type to implement `Clone`.
+    Arc,
+    /// Values are stored in a `Box` and copied via the given function.
+    Custom(fn(&T) -> T),
+}
+
+impl<T> fmt::Debug for BoxedCopyStrategy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BoxedCopyStrategy::Clone(_) => f.write_str("Clone"),
+            BoxedCopyStrategy::Arc => f.write_str("Arc"),
+            BoxedCopyStrategy::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
 /// Trait for defining boxed types.
 ///
 /// Links together the type name with the type itself.
@@ -18,12 +49,20 @@ use value::*;
 /// with the type system.
 ///
 /// [`register_boxed_type`]: fn.register_boxed_type.html
-pub trait BoxedType: Clone + Sized + 'static {
+pub trait BoxedType: Sized + 'static {
     /// Boxed type name.
     ///
     /// This must be unique in the whole process.
     const NAME: &'static str;
 
+    /// Strategy for copying and freeing values of the type.
+    ///
+    /// This is usually defined via the [`glib_boxed_type!`] macro and defaults
+    /// to copying values via `Clone`.
+    ///
+    /// [`glib_boxed_type!`]: ../../macro.glib_boxed_type.html
+    const COPY_STRATEGY: BoxedCopyStrategy<Self>;
+
     /// Returns the type ID.
     ///
     /// This is usually defined via the [`glib_boxed_type!`] macro.
@@ -42,14 +81,29 @@ pub trait BoxedType: Clone + Sized + 'static {
 /// [`glib_boxed_type!`]: ../../macro.glib_boxed_type.html
 pub fn register_boxed_type<T: BoxedType>() -> ::Type {
     unsafe extern "C" fn boxed_copy<T: BoxedType>(v: glib_sys::gpointer) -> glib_sys::gpointer {
-        let v = &*(v as *mut T);
-        let copy = Box::new(v.clone());
+        match T::COPY_STRATEGY {
+            BoxedCopyStrategy::Clone(copy) | BoxedCopyStrategy::Custom(copy) => {
+                let v = &*(v as *mut T);
+                Box::into_raw(Box::new(copy(v))) as glib_sys::gpointer
+            }
+            BoxedCopyStrategy::Arc => {
+                let v = Arc::from_raw(v as *const T);
+                let copy = Arc::clone(&v);
+                mem::forget(v);
 
-        Box::into_raw(copy) as glib_sys::gpointer
+                Arc::into_raw(copy) as glib_sys::gpointer
+            }
+        }
     }
     unsafe extern "C" fn boxed_free<T: BoxedType>(v: glib_sys::gpointer) {
-        let v = v as *mut T;
-        let _ = Box::from_raw(v);
+        match T::COPY_STRATEGY {
+            BoxedCopyStrategy::Clone(_) | BoxedCopyStrategy::Custom(_) => {
+                let _ = Box::from_raw(v as *mut T);
+            }
+            BoxedCopyStrategy::Arc => {
+                let _ = Arc::from_raw(v as *const T);
+            }
+        }
     }
     unsafe {
         use std::ffi::CString;
@@ -70,15 +124,47 @@ pub fn register_boxed_type<T: BoxedType>() -> ::Type {
     }
 }
 
+/// Moves `v` into newly allocated storage as expected by the copy and free
+/// functions of the boxed type `T`.
+#[doc(hidden)]
+pub fn boxed_into_raw<T: BoxedType>(v: T) -> glib_sys::gpointer {
+    match T::COPY_STRATEGY {
+        BoxedCopyStrategy::Clone(_) | BoxedCopyStrategy::Custom(_) => {
+            Box::into_raw(Box::new(v)) as glib_sys::gpointer
+        }
+        BoxedCopyStrategy::Arc => Arc::into_raw(Arc::new(v)) as glib_sys::gpointer,
+    }
+}
+
 #[macro_export]
 /// Macro for defining a `get_type` function.
 ///
 /// This returns a `glib::Type` and registers `Self` via [`register_boxed_type`]
 /// the first time it is called.
 ///
+/// Optionally the [`BoxedCopyStrategy`] can be given, e.g.
+/// `glib_boxed_type!(copy_strategy: Arc)`. By default values are copied via
+/// `Clone`, only the `Arc` strategy works for types that don't implement it.
+///
 /// [`register_boxed_type`]: subclass/boxed/fn.register_boxed_type.html
+/// [`BoxedCopyStrategy`]: subclass/boxed/enum.BoxedCopyStrategy.html
 macro_rules! glib_boxed_type {
+    (copy_strategy: Clone) => {
+        const COPY_STRATEGY: $crate::subclass::boxed::BoxedCopyStrategy<Self> =
+            $crate::subclass::boxed::BoxedCopyStrategy::Clone(<Self as ::std::clone::Clone>::clone);
+
+        $crate::glib_boxed_type!(@get_type);
+    };
+    (copy_strategy: $($strategy:tt)+) => {
+        const COPY_STRATEGY: $crate::subclass::boxed::BoxedCopyStrategy<Self> =
+            $crate::subclass::boxed::BoxedCopyStrategy::$($strategy)+;
+
+        $crate::glib_boxed_type!(@get_type);
+    };
     () => {
+        $crate::glib_boxed_type!(copy_strategy: Clone);
+    };
+    (@get_type) => {
         fn get_type() -> $crate::Type {
             static mut TYPE_: $crate::Type = $crate::Type::Invalid;
             static ONCE: ::std::sync::Once = ::std::sync::Once::new();
@@ -98,7 +184,11 @@ macro_rules! glib_boxed_type {
 #[macro_export]
 /// Macro for deriving the `glib::Value` traits for a [`BoxedType`].
 ///
+/// This requires the type to implement `Clone`. Values of other types can be
+/// stored in a `glib::Value` by moving them into a [`Boxed`].
+///
 /// [`BoxedType`]: trait.BoxedType.html
+/// [`Boxed`]: subclass/boxed/struct.Boxed.html
 macro_rules! glib_boxed_derive_traits {
     ($name:ident) => {
         impl $crate::StaticType for $name {
@@ -109,7 +199,7 @@ macro_rules! glib_boxed_derive_traits {
 
         impl $crate::value::SetValue for $name {
             unsafe fn set_value(value: &mut $crate::value::Value, this: &Self) {
-                let ptr: *mut $name = Box::into_raw(Box::new(this.clone()));
+                let ptr = $crate::subclass::boxed::boxed_into_raw::<$name>(this.clone());
                 $crate::gobject_sys::g_value_take_boxed(
                     $crate::translate::ToGlibPtrMut::to_glib_none_mut(value).0,
                     ptr as *mut _,
@@ -120,7 +210,7 @@ macro_rules! glib_boxed_derive_traits {
         impl $crate::value::SetValueOptional for $name {
             unsafe fn set_value_optional(value: &mut $crate::value::Value, this: Option<&Self>) {
                 let this = this.expect("None not allowed");
-                let ptr: *mut $name = Box::into_raw(Box::new(this.clone()));
+                let ptr = $crate::subclass::boxed::boxed_into_raw::<$name>(this.clone());
                 $crate::gobject_sys::g_value_take_boxed(
                     $crate::translate::ToGlibPtrMut::to_glib_none_mut(value).0,
                     ptr as *mut _,
@@ -157,6 +247,7 @@ macro_rules! glib_boxed_derive_traits {
 ///
 /// [`glib_boxed_derive_traits!`]: ../../macro.glib_boxed_derive_traits.html
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
 pub struct Boxed<T: BoxedType>(pub T);
 
 impl<T: BoxedType> ops::Deref for Boxed<T> {
@@ -179,21 +270,34 @@ impl<T: BoxedType> ::StaticType for Boxed<T> {
     }
 }
 
-impl<T: BoxedType> SetValue for Boxed<T> {
+impl<T: BoxedType + Clone> SetValue for Boxed<T> {
     unsafe fn set_value(value: &mut Value, this: &Self) {
-        let ptr: *mut Boxed<T> = Box::into_raw(Box::new(this.clone()));
+        let ptr = boxed_into_raw::<T>(this.0.clone());
         gobject_sys::g_value_take_boxed(value.to_glib_none_mut().0, ptr as *mut _);
     }
 }
 
-impl<T: BoxedType> SetValueOptional for Boxed<T> {
+impl<T: BoxedType + Clone> SetValueOptional for Boxed<T> {
     unsafe fn set_value_optional(value: &mut Value, this: Option<&Self>) {
         let this = this.expect("None not allowed");
-        let ptr: *mut Boxed<T> = Box::into_raw(Box::new(this.clone()));
+        let ptr = boxed_into_raw::<T>(this.0.clone());
         gobject_sys::g_value_take_boxed(value.to_glib_none_mut().0, ptr as *mut _);
     }
 }
 
+impl<T: BoxedType> From<Boxed<T>> for Value {
+    /// Moves the value into a new `glib::Value`, which doesn't require `T` to
+    /// implement `Clone`.
+    fn from(b: Boxed<T>) -> Self {
+        let mut value = Value::from_type(T::get_type());
+        unsafe {
+            let ptr = boxed_into_raw::<T>(b.0);
+            gobject_sys::g_value_take_boxed(value.to_glib_none_mut().0, ptr as *mut _);
+        }
+        value
+    }
+}
+
 impl<'a, T: BoxedType> FromValueOptional<'a> for &'a Boxed<T> {
     unsafe fn from_value_optional(value: &'a Value) -> Option<Self> {
         let ptr = gobject_sys::g_value_get_boxed(value.to_glib_none().0);
@@ -210,9 +314,124 @@ impl<'a, T: BoxedType> FromValue<'a> for &'a Boxed<T> {
     }
 }
 
+static BOXED_VALUE_TYPES: once_cell::sync::Lazy<Mutex<HashMap<TypeId, ::Type>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Wrapper struct for storing any `Clone` type in `glib::Value` without
+/// implementing [`BoxedType`] for it.
+///
+/// This allows using types from other crates, which can't implement
+/// [`BoxedType`], e.g. as object properties. The boxed type has to be
+/// registered once via [`register`] before it is used.
+///
+/// [`BoxedType`]: trait.BoxedType.html
+/// [`register`]: #method.register
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct BoxedValue<T: Clone + Send + Sync + 'static>(pub T);
+
+impl<T: Clone + Send + Sync + 'static> BoxedValue<T> {
+    /// Registers a boxed type with the given `name` for `T`.
+    ///
+    /// The type is only registered on the first call, further calls return the
+    /// already registered type regardless of `name`.
+    ///
+    /// Panics if a different type with the same name was registered already.
+    pub fn register(name: &'static str) -> ::Type {
+        unsafe extern "C" fn boxed_copy<T: Clone>(v: glib_sys::gpointer) -> glib_sys::gpointer {
+            let v = &*(v as *mut T);
+            Box::into_raw(Box::new(v.clone())) as glib_sys::gpointer
+        }
+        unsafe extern "C" fn boxed_free<T>(v: glib_sys::gpointer) {
+            let _ = Box::from_raw(v as *mut T);
+        }
+
+        let mut types = BOXED_VALUE_TYPES.lock().unwrap();
+        if let Some(type_) = types.get(&TypeId::of::<T>()) {
+            return *type_;
+        }
+
+        let type_ = unsafe {
+            use std::ffi::CString;
+
+            let type_name = CString::new(name).unwrap();
+            if gobject_sys::g_type_from_name(type_name.as_ptr()) != gobject_sys::G_TYPE_INVALID {
+                panic!("Type {} has already been registered", name);
+            }
+
+            from_glib(gobject_sys::g_boxed_type_register_static(
+                type_name.as_ptr(),
+                Some(boxed_copy::<T>),
+                Some(boxed_free::<T>),
+            ))
+        };
+        types.insert(TypeId::of::<T>(), type_);
+
+        type_
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> ops::Deref for BoxedValue<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> ops::DerefMut for BoxedValue<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> ::StaticType for BoxedValue<T> {
+    /// Panics if the type was not registered via [`register`](#method.register) yet.
+    fn static_type() -> ::Type {
+        *BOXED_VALUE_TYPES
+            .lock()
+            .unwrap()
+            .get(&TypeId::of::<T>())
+            .expect("BoxedValue type not registered yet")
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> SetValue for BoxedValue<T> {
+    unsafe fn set_value(value: &mut Value, this: &Self) {
+        let ptr = Box::into_raw(Box::new(this.0.clone()));
+        gobject_sys::g_value_take_boxed(value.to_glib_none_mut().0, ptr as *mut _);
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> SetValueOptional for BoxedValue<T> {
+    unsafe fn set_value_optional(value: &mut Value, this: Option<&Self>) {
+        let this = this.expect("None not allowed");
+        let ptr = Box::into_raw(Box::new(this.0.clone()));
+        gobject_sys::g_value_take_boxed(value.to_glib_none_mut().0, ptr as *mut _);
+    }
+}
+
+impl<'a, T: Clone + Send + Sync + 'static> FromValueOptional<'a> for &'a BoxedValue<T> {
+    unsafe fn from_value_optional(value: &'a Value) -> Option<Self> {
+        let ptr = gobject_sys::g_value_get_boxed(value.to_glib_none().0);
+        assert!(!ptr.is_null());
+        Some(&*(ptr as *mut BoxedValue<T>))
+    }
+}
+
+impl<'a, T: Clone + Send + Sync + 'static> FromValue<'a> for &'a BoxedValue<T> {
+    unsafe fn from_value(value: &'a Value) -> Self {
+        let ptr = gobject_sys::g_value_get_boxed(value.to_glib_none().0);
+        assert!(!ptr.is_null());
+        &*(ptr as *mut BoxedValue<T>)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use StaticType;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     #[derive(Clone, Debug, PartialEq, Eq)]
     struct MyBoxed(String);
@@ -225,6 +444,26 @@ mod test {
 
     glib_boxed_derive_traits!(MyBoxed);
 
+    static ARC_BOXED_CLONES: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct MyArcBoxed(String);
+
+    impl Clone for MyArcBoxed {
+        fn clone(&self) -> Self {
+            ARC_BOXED_CLONES.fetch_add(1, Ordering::SeqCst);
+            MyArcBoxed(self.0.clone())
+        }
+    }
+
+    impl BoxedType for MyArcBoxed {
+        const NAME: &'static str = "MyArcBoxed";
+
+        glib_boxed_type!(copy_strategy: Arc);
+    }
+
+    glib_boxed_derive_traits!(MyArcBoxed);
+
     #[test]
     fn test_register() {
         assert_ne!(::Type::Invalid, MyBoxed::get_type());
@@ -249,4 +488,60 @@ mod test {
         let b2 = v.get_some::<&MyBoxed>().unwrap();
         assert_eq!(&b, b2);
     }
+
+    #[test]
+    fn test_value_arc() {
+        let b = MyArcBoxed(String::from("abc"));
+        let v = b.to_value();
+        assert_eq!(ARC_BOXED_CLONES.load(Ordering::SeqCst), 1);
+
+        let v2 = v.clone();
+        let v3 = v2.clone();
+        drop(v2);
+        assert_eq!(ARC_BOXED_CLONES.load(Ordering::SeqCst), 1);
+
+        let b2 = v.get_some::<&MyArcBoxed>().unwrap();
+        let b3 = v3.get_some::<&MyArcBoxed>().unwrap();
+        assert_eq!(&b, b2);
+        assert!(::std::ptr::eq(b2, b3));
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct MyUncloneableBoxed(String);
+
+    impl BoxedType for MyUncloneableBoxed {
+        const NAME: &'static str = "MyUncloneableBoxed";
+
+        glib_boxed_type!(copy_strategy: Arc);
+    }
+
+    #[test]
+    fn test_value_arc_uncloneable() {
+        let v = Value::from(Boxed(MyUncloneableBoxed(String::from("abc"))));
+        assert_eq!(v.type_(), MyUncloneableBoxed::get_type());
+
+        let v2 = v.clone();
+        let b = v.get_some::<&Boxed<MyUncloneableBoxed>>().unwrap();
+        let b2 = v2.get_some::<&Boxed<MyUncloneableBoxed>>().unwrap();
+        assert_eq!(b.0, MyUncloneableBoxed(String::from("abc")));
+        assert!(::std::ptr::eq(b, b2));
+    }
+
+    #[test]
+    fn test_boxed_value() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Foreign(Vec<u32>);
+
+        let type_ = BoxedValue::<Foreign>::register("BoxedValueForeign");
+        assert_ne!(type_, ::Type::Invalid);
+        assert_eq!(BoxedValue::<Foreign>::register("BoxedValueForeign"), type_);
+        assert_eq!(BoxedValue::<Foreign>::static_type(), type_);
+
+        let b = BoxedValue(Foreign(vec![1, 2, 3]));
+        let v = b.to_value();
+        assert_eq!(v.type_(), type_);
+        let b2 = v.get_some::<&BoxedValue<Foreign>>().unwrap();
+        assert_eq!(&b, b2);
+        assert_eq!((b2.0).0, vec![1, 2, 3]);
+    }
 }
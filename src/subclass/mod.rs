@@ -180,6 +180,8 @@ pub mod object;
 #[macro_use]
 pub mod boxed;
 
+pub mod signal;
+
 pub mod prelude {
     //! Prelude that re-exports all important traits from this crate.
     pub use super::boxed::BoxedType;
@@ -187,12 +189,15 @@ pub mod prelude {
     pub use super::object::{ObjectClassSubclassExt, ObjectImpl, ObjectImplExt};
     pub use super::types::{
         ClassStruct, InstanceStruct, IsImplementable, IsSubclassable, ObjectSubclass,
+        ObjectSubclassIsExt,
     };
 }
 
-pub use self::boxed::register_boxed_type;
+pub use self::boxed::{register_boxed_type, BoxedValue};
 pub use self::interface::register_interface;
-pub use self::object::Property;
+pub use self::object::{Property, PropertyDef, PropertyDefBuilder};
+pub use self::signal::{Signal, SignalBuilder};
 pub use self::types::{
-    register_type, InitializingType, SignalClassHandlerToken, SignalInvocationHint, TypeData,
+    register_dynamic_type, register_type, InitializingType, SignalClassHandlerToken,
+    SignalInvocationHint, TypeData,
 };
@@ -9,12 +9,14 @@ use super::prelude::*;
 use super::types;
 use glib_sys;
 use gobject_sys;
+use object::ObjectExt;
 use std::borrow::Borrow;
+use std::ffi::CStr;
 use std::fmt;
 use std::mem;
 use std::ptr;
 use translate::*;
-use {Object, ObjectClass, ObjectType, SignalFlags, Type, Value};
+use {Closure, Object, ObjectClass, ObjectType, SignalFlags, Type, Value};
 
 #[macro_export]
 /// Macro for boilerplate of [`ObjectImpl`] implementations.
@@ -28,6 +30,127 @@ macro_rules! glib_object_impl {
     };
 }
 
+#[macro_export]
+/// Macro for declaring an enum of property ids.
+///
+/// The variants correspond to the property ids passed to
+/// [`ObjectImpl::get_property`] and [`ObjectImpl::set_property`] in declaration
+/// order, and the ids can be converted with `TryFrom<usize>` or `TryFrom<u32>`:
+///
+/// ```ignore
+/// glib_props_enum! {
+///     enum PropId {
+///         Name,
+///         Count,
+///     }
+/// }
+///
+/// fn get_property(&self, _obj: &glib::Object, id: usize) -> Result<glib::Value, ()> {
+///     match PropId::try_from(id).map_err(|_| ())? {
+///         PropId::Name => ...,
+///         PropId::Count => ...,
+///     }
+/// }
+/// ```
+///
+/// [`ObjectImpl::get_property`]: subclass/object/trait.ObjectImpl.html#method.get_property
+/// [`ObjectImpl::set_property`]: subclass/object/trait.ObjectImpl.html#method.set_property
+macro_rules! glib_props_enum {
+    ($(#[$attr:meta])* $vis:vis enum $name:ident { $($variant:ident),+ $(,)? }) => {
+        $(#[$attr])*
+        #[repr(u32)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        $vis enum $name {
+            $($variant),+
+        }
+
+        impl $name {
+            /// All property ids in declaration order.
+            pub const ALL: &'static [$name] = &[$($name::$variant),+];
+        }
+
+        impl ::std::convert::TryFrom<usize> for $name {
+            type Error = $crate::BoolError;
+
+            fn try_from(id: usize) -> ::std::result::Result<Self, Self::Error> {
+                $name::ALL.get(id).cloned().ok_or_else(|| {
+                    $crate::BoolError::new(
+                        format!("Invalid property id {}", id),
+                        file!(),
+                        module_path!(),
+                        line!(),
+                    )
+                })
+            }
+        }
+
+        impl ::std::convert::TryFrom<u32> for $name {
+            type Error = $crate::BoolError;
+
+            fn try_from(id: u32) -> ::std::result::Result<Self, Self::Error> {
+                <$name as ::std::convert::TryFrom<usize>>::try_from(id as usize)
+            }
+        }
+
+        impl ::std::convert::From<$name> for u32 {
+            fn from(id: $name) -> u32 {
+                id as u32
+            }
+        }
+    };
+}
+
+#[macro_export]
+/// Macro for declaring the properties of a subclass together with an enum of their ids.
+///
+/// This generates a static slice of [`Property`] that can be passed to
+/// [`ObjectClassSubclassExt::install_properties`] and an enum of the property ids as
+/// generated by [`glib_props_enum!`], keeping the order of both in sync:
+///
+/// ```ignore
+/// glib_object_subclass_properties! {
+///     enum PropId in PROPERTIES {
+///         Name("name") => |name| {
+///             glib::ParamSpec::string(name, "Name", "Name", None, glib::ParamFlags::READWRITE)
+///         },
+///         Count("count") => |name| {
+///             glib::ParamSpec::int(name, "Count", "Count", 0, 100, 0, glib::ParamFlags::READWRITE)
+///         },
+///     }
+/// }
+///
+/// fn class_init(klass: &mut subclass::simple::ClassStruct<Self>) {
+///     klass.install_properties(PROPERTIES);
+/// }
+///
+/// fn get_property(&self, _obj: &glib::Object, id: usize) -> Result<glib::Value, ()> {
+///     match PropId::try_from(id).map_err(|_| ())? {
+///         PropId::Name => ...,
+///         PropId::Count => ...,
+///     }
+/// }
+/// ```
+///
+/// [`Property`]: subclass/object/struct.Property.html
+/// [`ObjectClassSubclassExt::install_properties`]: subclass/object/trait.ObjectClassSubclassExt.html#method.install_properties
+/// [`glib_props_enum!`]: macro.glib_props_enum.html
+macro_rules! glib_object_subclass_properties {
+    ($(#[$attr:meta])* $vis:vis enum $name:ident in $props:ident {
+        $($variant:ident($prop_name:expr) => $pspec:expr),+ $(,)?
+    }) => {
+        $vis static $props: &[$crate::subclass::Property<'static>] = &[
+            $($crate::subclass::Property($prop_name, $pspec)),+
+        ];
+
+        $crate::glib_props_enum! {
+            $(#[$attr])*
+            $vis enum $name {
+                $($variant),+
+            }
+        }
+    };
+}
+
 /// Trait for implementors of `glib::Object` subclasses.
 ///
 /// This allows overriding the virtual methods of `glib::Object`.
@@ -39,6 +162,24 @@ pub trait ObjectImpl: ObjectImplExt + 'static {
     /// [`glib_object_impl!`]: ../../macro.glib_object_impl.html
     fn get_type_data(&self) -> ptr::NonNull<types::TypeData>;
 
+    /// Property definitions.
+    ///
+    /// The returned properties are installed automatically when the class is
+    /// initialized. Getting or setting any of them calls the getter or setter
+    /// of the [`PropertyDef`] instead of [`get_property`] and [`set_property`].
+    ///
+    /// Optional
+    ///
+    /// [`PropertyDef`]: struct.PropertyDef.html
+    /// [`get_property`]: #method.get_property
+    /// [`set_property`]: #method.set_property
+    fn properties() -> &'static [PropertyDef]
+    where
+        Self: Sized,
+    {
+        &[]
+    }
+
     /// Property setter.
     ///
     /// This is called whenever the property of this specific subclass with the
@@ -74,7 +215,12 @@ unsafe extern "C" fn get_property<T: ObjectSubclass>(
     let instance = &*(obj as *mut T::Instance);
     let imp = instance.get_impl();
 
-    match imp.get_property(&from_glib_borrow(obj), (id - 1) as usize) {
+    let res = match find_property_def(T::properties(), id) {
+        Some(def) => Ok((def.getter)(&from_glib_borrow(obj))),
+        None => imp.get_property(&from_glib_borrow(obj), (id - 1) as usize),
+    };
+
+    match res {
         Ok(v) => {
             // We first unset the value we get passed in, in case it contained
             // any previous data. Then we directly overwrite it with our new
@@ -96,8 +242,38 @@ unsafe extern "C" fn set_property<T: ObjectSubclass>(
     obj: *mut gobject_sys::GObject,
     id: u32,
     value: *mut gobject_sys::GValue,
-    _pspec: *mut gobject_sys::GParamSpec,
+    pspec: *mut gobject_sys::GParamSpec,
 ) {
+    // GLib only validates values passed via g_object_set_property() and friends. Catch
+    // invalid values from other code paths early during development. Like GLib, validate
+    // the value in place so that the setter gets the closest valid value, and don't
+    // complain about properties that allow lax validation.
+    if cfg!(debug_assertions) {
+        let modified = gobject_sys::g_param_value_validate(pspec, value);
+        let flags: ::ParamFlags = from_glib((*pspec).flags);
+        let lax = flags.contains(::ParamFlags::LAX_VALIDATION);
+        if modified != glib_sys::GFALSE && !lax {
+            ::log_critical(&format!(
+                "{}: invalid value for property '{}', using {:?} instead",
+                T::NAME,
+                CStr::from_ptr(gobject_sys::g_param_spec_get_name(pspec)).to_string_lossy(),
+                *(value as *mut Value)
+            ));
+        }
+    }
+
+    if let Some(def) = find_property_def(T::properties(), id) {
+        match def.setter {
+            Some(ref setter) => setter(&from_glib_borrow(obj), &*(value as *mut Value)),
+            None => ::log_critical(&format!(
+                "{}: property '{}' has no setter",
+                T::NAME,
+                def.name
+            )),
+        }
+        return;
+    }
+
     let instance = &*(obj as *mut T::Instance);
     let imp = instance.get_impl();
     imp.set_property(
@@ -107,11 +283,41 @@ unsafe extern "C" fn set_property<T: ObjectSubclass>(
     );
 }
 
+// Ids of properties installed from `ObjectImpl::properties()` start here so that they can't
+// collide with the ids of properties installed via `install_properties()`.
+const PROPERTY_DEF_ID_OFFSET: u32 = 1 << 16;
+
+fn find_property_def(defs: &'static [PropertyDef], id: u32) -> Option<&'static PropertyDef> {
+    if id < PROPERTY_DEF_ID_OFFSET {
+        return None;
+    }
+
+    defs.get((id - PROPERTY_DEF_ID_OFFSET) as usize)
+}
+
+fn parent_constructed_quark() -> ::Quark {
+    ::Quark::from_string("glib-rs-parent-constructed")
+}
+
 unsafe extern "C" fn constructed<T: ObjectSubclass>(obj: *mut gobject_sys::GObject) {
     let instance = &*(obj as *mut T::Instance);
     let imp = instance.get_impl();
+    let wrap: Object = from_glib_borrow(obj);
+
+    // In debug builds check that the implementation chained up, forgetting to do so silently
+    // breaks the initialization of the parent classes.
+    if cfg!(debug_assertions) {
+        let _ = wrap.steal_qdata::<bool>(parent_constructed_quark());
+    }
+
+    imp.constructed(&wrap);
 
-    imp.constructed(&from_glib_borrow(obj));
+    if cfg!(debug_assertions) && wrap.steal_qdata::<bool>(parent_constructed_quark()).is_none() {
+        ::log_critical(&format!(
+            "{}::constructed() did not chain up to parent_constructed()",
+            T::NAME
+        ));
+    }
 }
 
 /// Definition of a property.
@@ -124,6 +330,123 @@ impl<'a> fmt::Debug for Property<'a> {
     }
 }
 
+type PropertyGetter = Box<dyn Fn(&Object) -> Value + Send + Sync + 'static>;
+type PropertySetter = Box<dyn Fn(&Object, &Value) + Send + Sync + 'static>;
+
+/// Definition of a property together with its getter and setter.
+///
+/// Different to [`Property`], the value of a `PropertyDef` is read and
+/// written by its own getter and setter so that no manual matching on the
+/// property index is needed in [`ObjectImpl::get_property`] and
+/// [`ObjectImpl::set_property`].
+///
+/// [`Property`]: struct.Property.html
+/// [`ObjectImpl::get_property`]: trait.ObjectImpl.html#method.get_property
+/// [`ObjectImpl::set_property`]: trait.ObjectImpl.html#method.set_property
+pub struct PropertyDef {
+    name: String,
+    pspec: fn(&str) -> ::ParamSpec,
+    getter: PropertyGetter,
+    setter: Option<PropertySetter>,
+}
+
+impl PropertyDef {
+    /// Create a new builder for a property with the given name, `ParamSpec` factory and getter.
+    pub fn builder<G: Fn(&Object) -> Value + Send + Sync + 'static>(
+        name: &str,
+        pspec: fn(&str) -> ::ParamSpec,
+        getter: G,
+    ) -> PropertyDefBuilder {
+        PropertyDefBuilder {
+            name: String::from(name),
+            pspec,
+            getter: Box::new(getter),
+            setter: None,
+        }
+    }
+
+    /// Name of the property.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Create the `ParamSpec` of the property.
+    pub fn pspec(&self) -> ::ParamSpec {
+        (self.pspec)(&self.name)
+    }
+}
+
+impl fmt::Debug for PropertyDef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("PropertyDef")
+            .field("name", &self.name)
+            .field("setter", &self.setter.is_some())
+            .finish()
+    }
+}
+
+/// Builder for [`PropertyDef`].
+///
+/// [`PropertyDef`]: struct.PropertyDef.html
+pub struct PropertyDefBuilder {
+    name: String,
+    pspec: fn(&str) -> ::ParamSpec,
+    getter: PropertyGetter,
+    setter: Option<PropertySetter>,
+}
+
+impl PropertyDefBuilder {
+    /// Set the setter of the property.
+    ///
+    /// This is required for all writable properties, writable properties without a setter
+    /// are not installed.
+    pub fn setter<S: Fn(&Object, &Value) + Send + Sync + 'static>(self, setter: S) -> Self {
+        Self {
+            setter: Some(Box::new(setter)),
+            ..self
+        }
+    }
+
+    /// Build the property definition.
+    pub fn build(self) -> PropertyDef {
+        PropertyDef {
+            name: self.name,
+            pspec: self.pspec,
+            getter: self.getter,
+            setter: self.setter,
+        }
+    }
+}
+
+impl fmt::Debug for PropertyDefBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("PropertyDefBuilder")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+pub(crate) unsafe fn install_property_defs(
+    klass: *mut gobject_sys::GObjectClass,
+    defs: &'static [PropertyDef],
+) {
+    for (id, def) in defs.iter().enumerate() {
+        let pspec = def.pspec();
+        if def.setter.is_none() && pspec.get_flags().contains(::ParamFlags::WRITABLE) {
+            ::log_critical(&format!(
+                "Writable property '{}' has no setter, not installing it",
+                def.name
+            ));
+            continue;
+        }
+        gobject_sys::g_object_class_install_property(
+            klass,
+            PROPERTY_DEF_ID_OFFSET + id as u32,
+            pspec.to_glib_none().0,
+        );
+    }
+}
+
 /// Extension trait for `glib::Object`'s class struct.
 ///
 /// This contains various class methods and allows subclasses to override the virtual methods.
@@ -162,6 +485,29 @@ pub unsafe trait ObjectClassSubclassExt: Sized + 'static {
         }
     }
 
+    /// Install a single property on the subclass.
+    ///
+    /// This is the same as [`install_properties`] for a single property, with `id` being the
+    /// index in the properties array plus one. The property setters and getters are called with
+    /// `id - 1`.
+    ///
+    /// Panics if `id` is 0, which is reserved by GObject. Ids from 65536 on are used for the
+    /// properties returned by [`ObjectImpl::properties`].
+    ///
+    /// [`install_properties`]: #method.install_properties
+    /// [`ObjectImpl::properties`]: trait.ObjectImpl.html#method.properties
+    fn install_property(&mut self, id: u32, pspec: ::ParamSpec) {
+        assert_ne!(id, 0, "Property id 0 is reserved");
+
+        unsafe {
+            gobject_sys::g_object_class_install_property(
+                self as *mut _ as *mut gobject_sys::GObjectClass,
+                id,
+                pspec.to_glib_none().0,
+            );
+        }
+    }
+
     /// Add a new signal to the subclass.
     ///
     /// This can be emitted later by `glib::Object::emit` and external code
@@ -206,6 +552,32 @@ pub unsafe trait ObjectClassSubclassExt: Sized + 'static {
         }
     }
 
+    /// Add a new signal with a class closure to the subclass.
+    ///
+    /// This is the same as [`add_signal_with_class_handler`] but takes the class handler as
+    /// an arbitrary `Closure`, which is invoked with the instance and the signal arguments.
+    ///
+    /// [`add_signal_with_class_handler`]: #method.add_signal_with_class_handler
+    fn add_signal_with_class_closure(
+        &mut self,
+        name: &str,
+        flags: SignalFlags,
+        arg_types: &[Type],
+        ret_type: Type,
+        class_closure: Closure,
+    ) {
+        unsafe {
+            super::types::add_signal_with_class_closure(
+                *(self as *mut _ as *mut glib_sys::GType),
+                name,
+                flags,
+                arg_types,
+                ret_type,
+                &class_closure,
+            );
+        }
+    }
+
     /// Add a new signal with accumulator to the subclass.
     ///
     /// This can be emitted later by `glib::Object::emit` and external code
@@ -285,6 +657,21 @@ pub unsafe trait ObjectClassSubclassExt: Sized + 'static {
             );
         }
     }
+
+    /// Replace the class closure of the signal `signal_name` for the subclass.
+    ///
+    /// The signal can be defined by any parent class or interface and is called for emissions
+    /// on instances of the subclass instead of the parent's class closure. This allows changing
+    /// the default behaviour of inherited signals, such as action signals.
+    fn override_class_closure(&mut self, signal_name: &str, closure: Closure) {
+        unsafe {
+            super::types::signal_override_class_closure(
+                signal_name,
+                *(self as *mut _ as *mut glib_sys::GType),
+                &closure,
+            );
+        }
+    }
 }
 
 unsafe impl ObjectClassSubclassExt for ObjectClass {}
@@ -300,15 +687,38 @@ unsafe impl<T: ObjectSubclass> IsSubclassable<T> for ObjectClass {
     }
 }
 
+unsafe impl<T: ObjectSubclass> IsSubclassable<T> for ::InitiallyUnownedClass {
+    fn override_vfuncs(&mut self) {
+        <ObjectClass as IsSubclassable<T>>::override_vfuncs(self);
+    }
+}
+
 pub trait ObjectImplExt {
     /// Chain up to the parent class' implementation of `glib::Object::constructed()`.
     fn parent_constructed(&self, obj: &Object);
 
+    /// Chain up to the parent class' class handler of the signal that is currently emitted.
+    ///
+    /// This is for use from a class handler installed with
+    /// [`override_signal_class_handler`], and can only be called from within it as the
+    /// `token` is only available there. `values` are the arguments of the signal emission,
+    /// including the instance as first value.
+    ///
+    /// [`override_signal_class_handler`]: trait.ObjectClassSubclassExt.html#method.override_signal_class_handler
     fn signal_chain_from_overridden(
         &self,
         token: &super::SignalClassHandlerToken,
         values: &[Value],
     ) -> Option<Value>;
+
+    /// Looks up the `ParamSpec` of the property `name` of this type.
+    fn property_pspec(&self, name: &str) -> Option<::ParamSpec>;
+
+    /// Emits `notify` for all properties in `names` at once.
+    ///
+    /// Notifications are frozen while the properties are notified so that each
+    /// handler is only called once per property after all of them were queued.
+    fn notify_batch(&self, obj: &Object, names: &[&str]);
 }
 
 impl<T: ObjectImpl + ObjectSubclass> ObjectImplExt for T {
@@ -320,6 +730,11 @@ impl<T: ObjectImpl + ObjectSubclass> ObjectImplExt for T {
             if let Some(ref func) = (*parent_class).constructed {
                 func(obj.to_glib_none().0);
             }
+
+            // Checked by the `constructed` trampoline in debug builds
+            if cfg!(debug_assertions) {
+                obj.set_qdata(parent_constructed_quark(), true);
+            }
         }
     }
 
@@ -336,6 +751,27 @@ impl<T: ObjectImpl + ObjectSubclass> ObjectImplExt for T {
             )
         }
     }
+
+    fn notify_batch(&self, obj: &Object, names: &[&str]) {
+        unsafe {
+            let ptr: *mut gobject_sys::GObject = obj.to_glib_none().0;
+            gobject_sys::g_object_freeze_notify(ptr);
+            for name in names {
+                gobject_sys::g_object_notify(ptr, name.to_glib_none().0);
+            }
+            gobject_sys::g_object_thaw_notify(ptr);
+        }
+    }
+
+    fn property_pspec(&self, name: &str) -> Option<::ParamSpec> {
+        unsafe {
+            let data = self.get_type_data();
+            let klass = gobject_sys::g_type_class_peek(data.as_ref().get_type().to_glib());
+            assert!(!klass.is_null());
+
+            (*(klass as *const ObjectClass)).find_property(name)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -358,6 +794,15 @@ mod test {
 
         glib_object_subclass!();
 
+        fn type_init(type_: &mut subclass::InitializingType<Self>) {
+            type_.add_class_private::<u32>();
+        }
+
+        fn class_init(klass: &mut subclass::simple::ClassStruct<Self>) {
+            assert_eq!(*klass.class_private::<u32>(), 0);
+            *klass.class_private_mut::<u32>() = 42;
+        }
+
         fn new() -> Self {
             ChildObject
         }
@@ -462,6 +907,25 @@ mod test {
             );
         }
 
+        fn signals() -> &'static [subclass::Signal] {
+            static SIGNALS: once_cell::sync::Lazy<Vec<subclass::Signal>> =
+                once_cell::sync::Lazy::new(|| {
+                    vec![subclass::Signal::builder(
+                        "declared-signal",
+                        &[String::static_type()],
+                        String::static_type(),
+                    )
+                    .flags(SignalFlags::RUN_LAST | SignalFlags::ACTION)
+                    .class_handler(|_, args| {
+                        let arg = args[1].get::<String>().unwrap().unwrap();
+                        Some(format!("{}-handled", arg).to_value())
+                    })
+                    .build()]
+                });
+
+            &SIGNALS
+        }
+
         fn new() -> Self {
             Self {
                 name: RefCell::new(None),
@@ -487,7 +951,7 @@ mod test {
                 Property("child", ..) => {
                     // not stored, only used to test `set_property` with `Objects`
                 }
-                _ => unimplemented!(),
+                _ => unreachable!("unknown property id {}", id),
             }
         }
 
@@ -497,7 +961,7 @@ mod test {
             match *prop {
                 Property("name", ..) => Ok(self.name.borrow().to_value()),
                 Property("constructed", ..) => Ok(self.constructed.borrow().to_value()),
-                _ => unimplemented!(),
+                _ => unreachable!("unknown property id {}", id),
             }
         }
 
@@ -511,6 +975,118 @@ mod test {
         }
     }
 
+    static PROPERTY_DEF_OBJECT_PROPERTIES: [Property; 1] = [Property("label", |name| {
+        ::ParamSpec::string(
+            name,
+            "Label",
+            "Label installed via install_properties()",
+            None,
+            ::ParamFlags::READWRITE,
+        )
+    })];
+
+    pub struct PropertyDefObject {
+        count: RefCell<i32>,
+        label: RefCell<Option<String>>,
+    }
+
+    impl ObjectSubclass for PropertyDefObject {
+        const NAME: &'static str = "PropertyDefObject";
+        type ParentType = Object;
+        type Instance = subclass::simple::InstanceStruct<Self>;
+        type Class = subclass::simple::ClassStruct<Self>;
+
+        glib_object_subclass!();
+
+        fn class_init(klass: &mut subclass::simple::ClassStruct<Self>) {
+            klass.install_properties(&PROPERTY_DEF_OBJECT_PROPERTIES);
+        }
+
+        fn new() -> Self {
+            Self {
+                count: RefCell::new(0),
+                label: RefCell::new(None),
+            }
+        }
+    }
+
+    impl ObjectImpl for PropertyDefObject {
+        glib_object_impl!();
+
+        fn properties() -> &'static [PropertyDef] {
+            static PROPERTIES: once_cell::sync::Lazy<Vec<PropertyDef>> =
+                once_cell::sync::Lazy::new(|| {
+                    vec![
+                        PropertyDef::builder(
+                            "count",
+                            |name| {
+                                ::ParamSpec::int(
+                                    name,
+                                    "Count",
+                                    "Count",
+                                    0,
+                                    100,
+                                    0,
+                                    ::ParamFlags::READWRITE,
+                                )
+                            },
+                            |obj| {
+                                let imp = PropertyDefObject::from_instance(obj);
+                                imp.count.borrow().to_value()
+                            },
+                        )
+                        .setter(|obj, value| {
+                            let imp = PropertyDefObject::from_instance(obj);
+                            *imp.count.borrow_mut() = value
+                                .get_some()
+                                .expect("type conformity checked by `Object::set_property`");
+                        })
+                        .build(),
+                        PropertyDef::builder(
+                            "double-count",
+                            |name| {
+                                ::ParamSpec::int(
+                                    name,
+                                    "Double Count",
+                                    "Twice the count",
+                                    0,
+                                    200,
+                                    0,
+                                    ::ParamFlags::READABLE,
+                                )
+                            },
+                            |obj| {
+                                let imp = PropertyDefObject::from_instance(obj);
+                                (*imp.count.borrow() * 2).to_value()
+                            },
+                        )
+                        .build(),
+                    ]
+                });
+
+            &PROPERTIES
+        }
+
+        fn set_property(&self, _obj: &Object, id: usize, value: &Value) {
+            match PROPERTY_DEF_OBJECT_PROPERTIES[id] {
+                Property("label", ..) => {
+                    let label = value
+                        .get()
+                        .expect("type conformity checked by `Object::set_property`");
+                    self.label.replace(label);
+                }
+                _ => unreachable!("unknown property id {}", id),
+            }
+        }
+
+        fn get_property(&self, _obj: &Object, id: usize) -> Result<Value, ()> {
+            match PROPERTY_DEF_OBJECT_PROPERTIES[id] {
+                Property("label", ..) => Ok(self.label.borrow().to_value()),
+                _ => unreachable!("unknown property id {}", id),
+            }
+        }
+    }
+
     #[repr(C)]
     pub struct DummyInterface {
         parent: gobject_sys::GTypeInterface,
@@ -526,64 +1102,400 @@ mod test {
         }
     }
 
-    // Usually this would be implemented on a Rust wrapper type defined
-    // with glib_wrapper!() but for the test the following is susyscient
-    impl StaticType for DummyInterface {
-        fn static_type() -> Type {
-            DummyInterface::get_type()
+    // An object that is registered as a dynamic type with a `TypeModule`
+    pub struct DynamicObject;
+    impl ObjectSubclass for DynamicObject {
+        const NAME: &'static str = "DynamicObject";
+        type ParentType = Object;
+        type Instance = subclass::simple::InstanceStruct<Self>;
+        type Class = subclass::simple::ClassStruct<Self>;
+
+        glib_object_subclass!();
+
+        fn new() -> Self {
+            DynamicObject
         }
     }
 
-    // Usually this would be implemented on a Rust wrapper type defined
-    // with glib_wrapper!() but for the test the following is susyscient
-    unsafe impl<T: ObjectSubclass> IsImplementable<T> for DummyInterface {
-        unsafe extern "C" fn interface_init(
-            _iface: glib_sys::gpointer,
-            _iface_data: glib_sys::gpointer,
-        ) {
-        }
+    impl ObjectImpl for DynamicObject {
+        glib_object_impl!();
     }
 
-    #[test]
-    fn test_create() {
-        let type_ = SimpleObject::get_type();
-        let obj = Object::new(type_, &[]).unwrap();
+    // Registers a minimal `GTypeModule` subclass whose `load` and `unload` do nothing
+    fn test_type_module_type() -> Type {
+        unsafe extern "C" fn load(_module: *mut gobject_sys::GTypeModule) -> glib_sys::gboolean {
+            glib_sys::GTRUE
+        }
 
-        assert!(obj.get_type().is_a(&DummyInterface::static_type()));
+        unsafe extern "C" fn unload(_module: *mut gobject_sys::GTypeModule) {}
 
-        assert_eq!(
-            obj.get_property("constructed")
-                .unwrap()
-                .get_some::<bool>()
-                .unwrap(),
-            true
-        );
+        unsafe extern "C" fn class_init(klass: glib_sys::gpointer, _data: glib_sys::gpointer) {
+            let klass = &mut *(klass as *mut gobject_sys::GTypeModuleClass);
+            klass.load = Some(load);
+            klass.unload = Some(unload);
+        }
 
-        let weak = obj.downgrade();
-        drop(obj);
-        assert!(weak.upgrade().is_none());
+        unsafe {
+            let type_info = gobject_sys::GTypeInfo {
+                class_size: mem::size_of::<gobject_sys::GTypeModuleClass>() as u16,
+                base_init: None,
+                base_finalize: None,
+                class_init: Some(class_init),
+                class_finalize: None,
+                class_data: ptr::null_mut(),
+                instance_size: mem::size_of::<gobject_sys::GTypeModule>() as u16,
+                n_preallocs: 0,
+                instance_init: None,
+                value_table: ptr::null(),
+            };
+
+            from_glib(gobject_sys::g_type_register_static(
+                ::TypeModule::static_type().to_glib(),
+                b"TestTypeModule\0".as_ptr() as *const _,
+                &type_info,
+                0,
+            ))
+        }
     }
 
-    #[test]
-    fn test_set_properties() {
-        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+    // Counts the `notify` emissions on `NotifyClosureObject` instances
+    static NOTIFY_CLOSURE_CALLS: std::sync::atomic::AtomicUsize =
+        std::sync::atomic::AtomicUsize::new(0);
 
-        assert!(obj
-            .get_property("name")
-            .unwrap()
-            .get::<&str>()
-            .unwrap()
-            .is_none());
-        assert!(obj.set_property("name", &"test").is_ok());
-        assert_eq!(
-            obj.get_property("name").unwrap().get::<&str>().unwrap(),
-            Some("test")
-        );
+    // An object that replaces the class closure of the `notify` signal and defines a signal
+    // with a class closure
+    pub struct NotifyClosureObject;
+    impl ObjectSubclass for NotifyClosureObject {
+        const NAME: &'static str = "NotifyClosureObject";
+        type ParentType = Object;
+        type Instance = subclass::simple::InstanceStruct<Self>;
+        type Class = subclass::simple::ClassStruct<Self>;
 
-        assert_eq!(
-            obj.set_property("test", &true).err().unwrap().description(),
-            "property not found",
-        );
+        glib_object_subclass!();
+
+        fn class_init(klass: &mut subclass::simple::ClassStruct<Self>) {
+            klass.install_properties(&[Property("count", |name| {
+                ::ParamSpec::int(name, "Count", "Count", 0, 100, 0, ::ParamFlags::READWRITE)
+            })]);
+            klass.install_property(
+                2,
+                ::ParamSpec::boolean("flag", "Flag", "Flag", false, ::ParamFlags::READWRITE),
+            );
+
+            klass.override_class_closure(
+                "notify",
+                ::Closure::new(|values| {
+                    let pspec = values[1].get::<::ParamSpec>().unwrap().unwrap();
+                    assert_eq!(pspec.get_name(), "count");
+                    NOTIFY_CLOSURE_CALLS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    None
+                }),
+            );
+
+            klass.add_signal_with_class_closure(
+                "double",
+                SignalFlags::RUN_LAST,
+                &[Type::I32],
+                Type::I32,
+                ::Closure::new(|values| {
+                    let value = values[1].get_some::<i32>().unwrap();
+                    Some((value * 2).to_value())
+                }),
+            );
+        }
+
+        fn new() -> Self {
+            NotifyClosureObject
+        }
+    }
+
+    impl ObjectImpl for NotifyClosureObject {
+        glib_object_impl!();
+    }
+
+    // An object that gets `DummyInterface` added only after registration
+    pub struct LateInterfaceObject;
+    impl ObjectSubclass for LateInterfaceObject {
+        const NAME: &'static str = "LateInterfaceObject";
+        type ParentType = Object;
+        type Instance = subclass::simple::InstanceStruct<Self>;
+        type Class = subclass::simple::ClassStruct<Self>;
+
+        glib_object_subclass!();
+
+        fn new() -> Self {
+            LateInterfaceObject
+        }
+    }
+
+    impl ObjectImpl for LateInterfaceObject {
+        glib_object_impl!();
+    }
+
+    // A subclass of `InitiallyUnowned`, instances start with a floating reference
+    pub struct FloatingObject;
+    impl ObjectSubclass for FloatingObject {
+        const NAME: &'static str = "FloatingObject";
+        type ParentType = ::InitiallyUnowned;
+        type Instance = subclass::simple::InstanceStruct<Self>;
+        type Class = subclass::simple::ClassStruct<Self>;
+
+        glib_object_subclass!();
+
+        fn new() -> Self {
+            FloatingObject
+        }
+    }
+
+    impl ObjectImpl for FloatingObject {
+        glib_object_impl!();
+    }
+
+    // An interface with a property that is not implemented by any type
+    #[repr(C)]
+    pub struct PropertyInterface {
+        parent: gobject_sys::GTypeInterface,
+    }
+
+    impl ObjectInterface for PropertyInterface {
+        const NAME: &'static str = "PropertyInterface";
+
+        glib_object_interface!();
+
+        fn type_init(type_: &mut subclass::InitializingType<Self>) {
+            type_.add_prerequisite::<Object>();
+        }
+
+        fn interface_init(&mut self) {
+            self.install_properties(&[Property("size", |name| {
+                ::ParamSpec::uint(
+                    name,
+                    "Size",
+                    "Size of the thing",
+                    0,
+                    100,
+                    10,
+                    ::ParamFlags::READWRITE,
+                )
+            })]);
+        }
+    }
+
+    // Usually this would be implemented on a Rust wrapper type defined
+    // with glib_wrapper!() but for the test the following is susyscient
+    impl StaticType for DummyInterface {
+        fn static_type() -> Type {
+            DummyInterface::get_type()
+        }
+    }
+
+    // Usually this would be implemented on a Rust wrapper type defined
+    // with glib_wrapper!() but for the test the following is susyscient
+    unsafe impl<T: ObjectSubclass> IsImplementable<T> for DummyInterface {
+        unsafe extern "C" fn interface_init(
+            _iface: glib_sys::gpointer,
+            _iface_data: glib_sys::gpointer,
+        ) {
+        }
+    }
+
+    #[test]
+    fn test_create() {
+        let type_ = SimpleObject::get_type();
+        let obj = Object::new(type_, &[]).unwrap();
+
+        assert!(obj.get_type().is_a(&DummyInterface::static_type()));
+        assert_eq!(obj.get_type(), type_);
+        assert_ne!(obj.get_type(), Object::static_type());
+        assert_eq!(obj.to_string(), format!("SimpleObject@{:p}", obj.as_ptr()));
+
+        assert_eq!(
+            obj.get_property("constructed")
+                .unwrap()
+                .get_some::<bool>()
+                .unwrap(),
+            true
+        );
+
+        let weak = obj.downgrade();
+        drop(obj);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_class_private() {
+        let obj = Object::new(ChildObject::get_type(), &[]).unwrap();
+        let instance =
+            unsafe { &*(obj.as_ptr() as *const subclass::simple::InstanceStruct<ChildObject>) };
+
+        assert_eq!(*instance.get_class().class_private::<u32>(), 42);
+
+        let imp = ChildObject::from_instance(&obj);
+        assert!(ptr::eq(imp, obj.imp::<ChildObject>()));
+        let klass = unsafe { imp.get_type_data().as_ref().get_class::<ChildObject>() };
+        assert!(ptr::eq(klass, instance.get_class()));
+        assert!(ptr::eq(ChildObject::class(), instance.get_class()));
+    }
+
+    #[test]
+    fn test_class_private_subclass() {
+        // A plain subclass of `ChildObject` that inherits the class private data
+        let parent_type = ChildObject::get_type();
+        let subclass_type: Type = unsafe {
+            let mut query = mem::MaybeUninit::zeroed();
+            gobject_sys::g_type_query(parent_type.to_glib(), query.as_mut_ptr());
+            let query: gobject_sys::GTypeQuery = query.assume_init();
+
+            from_glib(gobject_sys::g_type_register_static_simple(
+                parent_type.to_glib(),
+                b"ChildObjectSubclass\0".as_ptr() as *const _,
+                query.class_size,
+                None,
+                query.instance_size,
+                None,
+                0,
+            ))
+        };
+
+        let obj = Object::new(subclass_type, &[]).unwrap();
+        let instance =
+            unsafe { &*(obj.as_ptr() as *const subclass::simple::InstanceStruct<ChildObject>) };
+        assert_eq!(obj.get_type(), subclass_type);
+        assert_eq!(*instance.get_class().class_private::<u32>(), 42);
+    }
+
+    #[test]
+    fn test_downcast_impl() {
+        let obj = Object::new(ChildObject::get_type(), &[]).unwrap();
+        let imp_ptr = ChildObject::from_instance(&obj) as *const ChildObject;
+
+        unsafe {
+            let instance_ptr = obj.as_ptr() as *mut subclass::simple::InstanceStruct<ChildObject>;
+            assert!(ptr::eq(InstanceStruct::downcast_impl(instance_ptr), imp_ptr));
+            assert!(ptr::eq(InstanceStruct::downcast_impl_mut(instance_ptr), imp_ptr));
+        }
+    }
+
+    #[test]
+    fn test_class_has_property() {
+        let obj = Object::new(PropertyDefObject::get_type(), &[]).unwrap();
+        let instance = unsafe {
+            &*(obj.as_ptr() as *const subclass::simple::InstanceStruct<PropertyDefObject>)
+        };
+        let klass = instance.get_class();
+
+        assert!(klass.has_property("count", None));
+        assert!(klass.has_property("count", Some(i32::static_type())));
+        assert!(!klass.has_property("count", Some(String::static_type())));
+        assert!(!klass.has_property("no-such-property", None));
+    }
+
+    #[test]
+    fn test_qdata() {
+        let obj = Object::new(ChildObject::get_type(), &[]).unwrap();
+        let key = ::Quark::from_string("test-qdata");
+
+        unsafe {
+            assert!(obj.get_qdata::<String>(key).is_none());
+
+            obj.set_qdata(key, String::from("data"));
+            assert_eq!(obj.get_qdata::<String>(key).map(String::as_str), Some("data"));
+
+            assert_eq!(obj.steal_qdata::<String>(key), Some(String::from("data")));
+            assert!(obj.get_qdata::<String>(key).is_none());
+            assert!(obj.steal_qdata::<String>(key).is_none());
+        }
+    }
+
+    #[test]
+    fn test_ref_sink() {
+        unsafe {
+            // `GInitiallyUnowned` itself is abstract, so give a plain object a
+            // floating reference instead
+            let ptr =
+                gobject_sys::g_object_newv(Object::static_type().to_glib(), 0, ptr::null_mut());
+            gobject_sys::g_object_force_floating(ptr);
+            assert_ne!(gobject_sys::g_object_is_floating(ptr as *mut _), 0);
+
+            let obj = Object::ref_sink(ptr);
+            assert_eq!(gobject_sys::g_object_is_floating(obj.as_ptr() as *mut _), 0);
+            assert_eq!(obj.ref_count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_typed_object() {
+        use std::collections::HashSet;
+
+        let obj = Object::new(Object::static_type(), &[]).unwrap();
+        let typed = ::TypedObject::<Object>::new(obj.clone()).unwrap();
+        assert_eq!(*typed, obj);
+
+        let mut set = HashSet::new();
+        set.insert(typed.clone());
+        assert!(set.contains(&typed));
+        assert_eq!(typed.into_inner(), obj);
+
+        let floating = Object::new(FloatingObject::get_type(), &[])
+            .unwrap()
+            .downcast::<::InitiallyUnowned>()
+            .unwrap();
+        assert!(::TypedObject::<::InitiallyUnowned>::new(floating.clone()).is_err());
+        assert!(::TypedObject::<Object>::new(floating).is_err());
+    }
+
+    #[test]
+    fn test_initially_unowned() {
+        let obj = Object::new(FloatingObject::get_type(), &[]).unwrap();
+        assert!(obj.is::<::InitiallyUnowned>());
+        assert_eq!(obj.ref_count(), 1);
+        unsafe {
+            assert_eq!(gobject_sys::g_object_is_floating(obj.as_ptr() as *mut _), 0);
+        }
+
+        let obj = obj.downcast::<::InitiallyUnowned>().unwrap();
+        let imp = FloatingObject::from_instance(&obj);
+        assert_eq!(imp.get_instance(), obj);
+        assert_eq!(obj.ref_count(), 1);
+    }
+
+    #[test]
+    fn test_object_identity() {
+        use std::collections::HashMap;
+
+        let obj = Object::new(ChildObject::get_type(), &[]).unwrap();
+        let other = Object::new(ChildObject::get_type(), &[]).unwrap();
+
+        assert_eq!(obj, obj.clone());
+        assert_ne!(obj, other);
+
+        let mut map = HashMap::new();
+        map.insert(obj.clone(), 1);
+        map.insert(other.clone(), 2);
+        assert_eq!(map.get(&obj), Some(&1));
+        assert_eq!(map.get(&other), Some(&2));
+    }
+
+    #[test]
+    fn test_set_properties() {
+        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+
+        assert!(obj
+            .get_property("name")
+            .unwrap()
+            .get::<&str>()
+            .unwrap()
+            .is_none());
+        assert!(obj.set_property("name", &"test").is_ok());
+        assert_eq!(
+            obj.get_property("name").unwrap().get::<&str>().unwrap(),
+            Some("test")
+        );
+
+        assert_eq!(
+            obj.set_property("test", &true).err().unwrap().description(),
+            "property not found",
+        );
 
         assert_eq!(
             obj.set_property("constructed", &false)
@@ -614,6 +1526,502 @@ mod test {
         assert!(obj.set_property("child", &child).is_ok());
     }
 
+    #[test]
+    fn test_binding_introspection() {
+        let source = Object::new(SimpleObject::get_type(), &[]).unwrap();
+        let target = Object::new(SimpleObject::get_type(), &[]).unwrap();
+
+        let binding = source
+            .bind_property("name", &target, "name")
+            .flags(::BindingFlags::SYNC_CREATE)
+            .build()
+            .unwrap();
+
+        assert_eq!(binding.source_object().as_ref(), Some(&source));
+        assert_eq!(binding.source_property(), "name");
+        assert_eq!(binding.target_object().as_ref(), Some(&target));
+        assert_eq!(binding.target_property(), "name");
+        assert_eq!(binding.flags(), ::BindingFlags::SYNC_CREATE);
+
+        source.set_property("name", &"bound").unwrap();
+        assert_eq!(
+            target.get_property("name").unwrap().get::<&str>().unwrap(),
+            Some("bound")
+        );
+
+        binding.unbind();
+        source.set_property("name", &"unbound").unwrap();
+        assert_eq!(
+            target.get_property("name").unwrap().get::<&str>().unwrap(),
+            Some("bound")
+        );
+    }
+
+    #[test]
+    fn test_property_defs() {
+        let obj = Object::new(PropertyDefObject::get_type(), &[("count", &21)]).unwrap();
+
+        assert_eq!(
+            obj.get_property("count").unwrap().get_some::<i32>().unwrap(),
+            21
+        );
+        assert_eq!(
+            obj.get_property("double-count")
+                .unwrap()
+                .get_some::<i32>()
+                .unwrap(),
+            42
+        );
+
+        assert!(obj.set_property("count", &5).is_ok());
+        assert_eq!(
+            obj.get_property("double-count")
+                .unwrap()
+                .get_some::<i32>()
+                .unwrap(),
+            10
+        );
+        assert!(obj.set_property("double-count", &5).is_err());
+
+        // Properties installed via `install_properties()` use their own ids
+        obj.set_property("label", &"label").unwrap();
+        assert_eq!(
+            obj.get_property("label").unwrap().get::<&str>().unwrap(),
+            Some("label")
+        );
+        assert_eq!(
+            obj.get_property("count").unwrap().get_some::<i32>().unwrap(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_new_with_properties() {
+        let obj = Object::new_with_properties(
+            PropertyDefObject::get_type(),
+            &["count"],
+            &[7.to_value()],
+        )
+        .unwrap();
+        assert_eq!(obj.property::<i32>("count"), 7);
+
+        let res = Object::new_with_properties(PropertyDefObject::get_type(), &["count"], &[]);
+        assert!(res.is_err());
+    }
+
+    glib_props_enum! {
+        enum PropId {
+            Name,
+            Constructed,
+            Child,
+        }
+    }
+
+    glib_object_subclass_properties! {
+        enum MacroPropId in MACRO_PROPERTIES {
+            Name("name") => |name| {
+                ::ParamSpec::string(name, "Name", "Name", None, ::ParamFlags::READWRITE)
+            },
+            Count("count") => |name| {
+                ::ParamSpec::int(name, "Count", "Count", 0, 100, 0, ::ParamFlags::READWRITE)
+            },
+        }
+    }
+
+    #[test]
+    fn test_object_subclass_properties() {
+        use std::convert::TryFrom;
+
+        assert_eq!(MACRO_PROPERTIES.len(), MacroPropId::ALL.len());
+        assert_eq!(MACRO_PROPERTIES[MacroPropId::Name as usize].0, "name");
+        assert_eq!(MACRO_PROPERTIES[MacroPropId::Count as usize].0, "count");
+        assert_eq!(MacroPropId::try_from(1usize).unwrap(), MacroPropId::Count);
+
+        let pspec = (MACRO_PROPERTIES[1].1)(MACRO_PROPERTIES[1].0);
+        assert_eq!(pspec.get_name(), "count");
+        assert_eq!(pspec.get_value_type(), i32::static_type());
+    }
+
+    #[test]
+    fn test_props_enum() {
+        use std::convert::TryFrom;
+
+        assert_eq!(PropId::try_from(0usize).unwrap(), PropId::Name);
+        assert_eq!(PropId::try_from(2u32).unwrap(), PropId::Child);
+        assert!(PropId::try_from(3usize).is_err());
+        assert_eq!(u32::from(PropId::Constructed), 1);
+        assert_eq!(PropId::ALL.len(), PROPERTIES.len());
+    }
+
+    #[test]
+    fn test_ref_count() {
+        let obj = Object::new(ChildObject::get_type(), &[]).unwrap();
+        assert_eq!(obj.ref_count(), 1);
+
+        let obj_clone = obj.clone();
+        assert_eq!(obj.ref_count(), 2);
+
+        drop(obj_clone);
+        assert_eq!(obj.ref_count(), 1);
+
+        let weak = obj.downgrade();
+        assert_eq!(obj.ref_count(), 1);
+        drop(weak);
+    }
+
+    #[test]
+    fn test_connect_notify_local() {
+        use std::rc::Rc;
+
+        let obj = Object::new(PropertyDefObject::get_type(), &[]).unwrap();
+
+        let notified = Rc::new(RefCell::new(Vec::new()));
+        let notified_clone = notified.clone();
+        obj.connect_notify_local(Some("count"), move |_, pspec| {
+            notified_clone.borrow_mut().push(pspec.get_name());
+        });
+
+        obj.notify("count");
+        obj.notify("double-count");
+        assert_eq!(*notified.borrow(), vec![String::from("count")]);
+    }
+
+    #[test]
+    fn test_connect_notify_static() {
+        use std::sync::{Arc, Mutex};
+
+        let obj = Object::new(PropertyDefObject::get_type(), &[]).unwrap();
+
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let notified_clone = notified.clone();
+        obj.connect_notify_static::<Object, _>("count", move |obj, pspec| {
+            assert_eq!(obj.get_type(), PropertyDefObject::get_type());
+            notified_clone.lock().unwrap().push(pspec.get_name());
+        });
+
+        obj.notify("count");
+        obj.notify("double-count");
+        assert_eq!(*notified.lock().unwrap(), vec![String::from("count")]);
+    }
+
+    #[test]
+    fn test_override_class_closure() {
+        use std::rc::Rc;
+        use std::sync::atomic::Ordering;
+
+        let obj = Object::new(NotifyClosureObject::get_type(), &[]).unwrap();
+
+        let handler_calls = Rc::new(RefCell::new(0));
+        let handler_calls_clone = handler_calls.clone();
+        obj.connect_notify_local(Some("count"), move |_, _| {
+            *handler_calls_clone.borrow_mut() += 1;
+        });
+
+        obj.notify("count");
+        obj.notify("count");
+        assert_eq!(NOTIFY_CLOSURE_CALLS.load(Ordering::SeqCst), 2);
+        assert_eq!(*handler_calls.borrow(), 2);
+    }
+
+    #[test]
+    fn test_install_property() {
+        let obj = Object::new(NotifyClosureObject::get_type(), &[]).unwrap();
+
+        let pspec = obj.find_property("flag").unwrap();
+        assert_eq!(pspec.get_value_type(), Type::Bool);
+        assert!(obj.find_property("count").is_some());
+    }
+
+    #[test]
+    fn test_add_signal_with_class_closure() {
+        let obj = Object::new(NotifyClosureObject::get_type(), &[]).unwrap();
+
+        let ret = obj.emit("double", &[&21]).unwrap().unwrap();
+        assert_eq!(ret.get_some::<i32>(), Ok(42));
+    }
+
+    #[test]
+    fn test_register_dynamic_type() {
+        let module = Object::new(test_type_module_type(), &[])
+            .unwrap()
+            .downcast::<::TypeModule>()
+            .unwrap();
+        module.set_name("test-module");
+        assert!(module.use_());
+
+        let type_ = subclass::register_dynamic_type::<DynamicObject>(&module);
+        assert_eq!(type_, DynamicObject::get_type());
+        assert_eq!(type_.name(), "DynamicObject");
+        assert!(type_.is_a(&Object::static_type()));
+
+        let obj = Object::new(type_, &[]).unwrap();
+        assert_eq!(obj.get_type(), type_);
+        let _ = DynamicObject::from_instance(&obj);
+        drop(obj);
+
+        // Registering again while the module is loaded returns the same type
+        assert_eq!(subclass::register_dynamic_type::<DynamicObject>(&module), type_);
+        module.unuse();
+
+        // Type modules must never be finalized
+        std::mem::forget(module);
+    }
+
+    #[test]
+    fn test_set_properties_batched() {
+        use std::rc::Rc;
+
+        let obj = Object::new(PropertyDefObject::get_type(), &[]).unwrap();
+
+        let notified = Rc::new(RefCell::new(Vec::new()));
+        let notified_clone = notified.clone();
+        obj.connect_notify_local(None, move |obj, pspec| {
+            let count = obj.get_property("count").unwrap().get_some::<i32>().unwrap();
+            notified_clone.borrow_mut().push((pspec.get_name(), count));
+        });
+
+        obj.set_properties(&[("count", &1), ("count", &2)]).unwrap();
+        assert_eq!(*notified.borrow(), vec![(String::from("count"), 2)]);
+
+        assert!(obj.set_properties(&[("count", &3), ("no-such-property", &4)]).is_err());
+        assert_eq!(obj.property::<i32>("count"), 3);
+    }
+
+    #[test]
+    fn test_with_frozen_notify() {
+        use std::rc::Rc;
+
+        let obj = Object::new(PropertyDefObject::get_type(), &[]).unwrap();
+
+        let notified = Rc::new(RefCell::new(Vec::new()));
+        let notified_clone = notified.clone();
+        obj.connect_notify_local(None, move |_, pspec| {
+            notified_clone.borrow_mut().push(pspec.get_name());
+        });
+
+        let ret = obj.with_frozen_notify(|obj| {
+            obj.notify("count");
+            obj.notify("double-count");
+            obj.notify("count");
+            assert!(notified.borrow().is_empty());
+            42
+        });
+        assert_eq!(ret, 42);
+
+        let mut names = notified.borrow().clone();
+        names.sort();
+        assert_eq!(names, vec![String::from("count"), String::from("double-count")]);
+
+        notified.borrow_mut().clear();
+        {
+            let _outer = obj.freeze_notify();
+            let inner = obj.freeze_notify();
+            obj.notify("count");
+            drop(inner);
+            assert!(notified.borrow().is_empty());
+        }
+        assert_eq!(*notified.borrow(), vec![String::from("count")]);
+    }
+
+    #[test]
+    fn test_notify_batch() {
+        use std::rc::Rc;
+
+        let obj = Object::new(PropertyDefObject::get_type(), &[]).unwrap();
+
+        let notified = Rc::new(RefCell::new(Vec::new()));
+        let notified_clone = notified.clone();
+        obj.connect_notify_local(None, move |_, pspec| {
+            notified_clone.borrow_mut().push(pspec.get_name());
+        });
+
+        let imp = PropertyDefObject::from_instance(&obj);
+        imp.notify_batch(&obj, &["count", "double-count", "count"]);
+
+        let mut notified = notified.borrow().clone();
+        notified.sort();
+        assert_eq!(notified, vec![String::from("count"), String::from("double-count")]);
+    }
+
+    #[test]
+    fn test_property_pspec() {
+        let obj = Object::new(PropertyDefObject::get_type(), &[]).unwrap();
+
+        let imp = PropertyDefObject::from_instance(&obj);
+        let pspec = imp.property_pspec("count").unwrap();
+        assert_eq!(pspec.get_name(), "count");
+        assert_eq!(pspec.get_value_type(), i32::static_type());
+        assert!(imp.property_pspec("no-such-property").is_none());
+    }
+
+    #[test]
+    fn test_properties_hash() {
+        let obj = Object::new(PropertyDefObject::get_type(), &[("count", &21)]).unwrap();
+
+        let properties = obj.properties_hash();
+        assert_eq!(properties.len(), 3);
+        assert_eq!(properties["count"].get_some::<i32>().unwrap(), 21);
+        assert_eq!(properties["double-count"].get_some::<i32>().unwrap(), 42);
+        assert_eq!(properties["label"].get::<String>().unwrap(), None);
+    }
+
+    #[test]
+    fn test_object_factory() {
+        use {FactoryError, ObjectFactory};
+
+        // Make sure the type is registered
+        let type_ = SimpleObject::get_type();
+
+        let obj = ObjectFactory::create("SimpleObject", &[("name", &"created")]).unwrap();
+        assert_eq!(obj.get_type(), type_);
+        assert_eq!(
+            obj.get_property("name").unwrap().get::<&str>(),
+            Ok(Some("created"))
+        );
+
+        match ObjectFactory::create("NoSuchType", &[]) {
+            Err(FactoryError::UnknownType(ref name)) if name == "NoSuchType" => (),
+            res => panic!("Unexpected result {:?}", res),
+        }
+        match ObjectFactory::create("gint", &[]) {
+            Err(FactoryError::NotAnObjectType(type_)) => assert_eq!(type_, Type::I32),
+            res => panic!("Unexpected result {:?}", res),
+        }
+        match ObjectFactory::create("GInitiallyUnowned", &[]) {
+            Err(FactoryError::AbstractType(_)) => (),
+            res => panic!("Unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_set_property_from_str() {
+        let obj = Object::new(PropertyDefObject::get_type(), &[]).unwrap();
+
+        obj.set_property_from_str("count", " 21").unwrap();
+        assert_eq!(obj.property::<i32>("count"), 21);
+        assert!(obj.set_property_from_str("count", "many").is_err());
+        assert!(obj.set_property_from_str("no-such-property", "1").is_err());
+
+        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+        obj.set_property_from_str("name", "from-str").unwrap();
+        assert_eq!(
+            obj.get_property("name").unwrap().get::<&str>(),
+            Ok(Some("from-str"))
+        );
+    }
+
+    #[test]
+    fn test_typed_property() {
+        let obj = Object::new(PropertyDefObject::get_type(), &[("count", &21)]).unwrap();
+
+        assert_eq!(obj.property::<i32>("count"), 21);
+        assert_eq!(obj.try_property::<i32>("double-count").unwrap(), 42);
+        assert!(obj.try_property::<bool>("count").is_err());
+        assert!(obj.try_property::<i32>("no-such-property").is_err());
+
+        obj.set_property("count", &5).unwrap();
+        assert_eq!(obj.property::<i32>("double-count"), 10);
+
+        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+        assert!(obj.try_property::<String>("name").is_err());
+        obj.set_property("name", &"typed").unwrap();
+        assert_eq!(obj.property::<String>("name"), "typed");
+        assert_eq!(obj.property::<::GString>("name"), "typed");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_typed_property_mismatch() {
+        let obj = Object::new(PropertyDefObject::get_type(), &[]).unwrap();
+        obj.property::<String>("count");
+    }
+
+    #[test]
+    fn test_try_property_error() {
+        use object::PropertyError;
+
+        let obj = Object::new(PropertyDefObject::get_type(), &[("count", &21)]).unwrap();
+
+        assert_eq!(obj.try_property::<i32>("count").unwrap(), 21);
+        match obj.try_property::<bool>("count") {
+            Err(PropertyError::TypeMismatch { expected, actual }) => {
+                assert_eq!(expected, Type::Bool);
+                assert_eq!(actual, Type::I32);
+            }
+            res => panic!("Unexpected result {:?}", res),
+        }
+        match obj.try_property::<i32>("no-such-property") {
+            Err(PropertyError::NotFound) => (),
+            res => panic!("Unexpected result {:?}", res),
+        }
+
+        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+        obj.set_property("name", &"typed").unwrap();
+        assert_eq!(obj.try_property::<String>("name").unwrap(), "typed");
+    }
+
+    #[test]
+    fn test_binding_group() {
+        let get_name = |obj: &Object| {
+            obj.get_property("name").unwrap().get::<String>().unwrap()
+        };
+
+        let source = Object::new(SimpleObject::get_type(), &[("name", &"source")]).unwrap();
+        let other_source = Object::new(SimpleObject::get_type(), &[("name", &"other")]).unwrap();
+        let target = Object::new(SimpleObject::get_type(), &[]).unwrap();
+
+        let group = ::BindingGroup::new();
+        group.bind("name", &target, "name", ::BindingFlags::SYNC_CREATE);
+        assert!(group.dup_source().is_none());
+        assert_eq!(get_name(&target), None);
+
+        group.set_source(Some(&source));
+        assert_eq!(group.dup_source().as_ref(), Some(&source));
+        assert_eq!(get_name(&target), Some("source".to_string()));
+
+        group.set_source(Some(&other_source));
+        assert_eq!(get_name(&target), Some("other".to_string()));
+        source.set_property("name", &"changed").unwrap();
+        assert_eq!(get_name(&target), Some("other".to_string()));
+
+        group.set_source(None);
+        other_source.set_property("name", &"changed").unwrap();
+        assert_eq!(get_name(&target), Some("other".to_string()));
+    }
+
+    #[test]
+    fn test_binding_group_reentrant() {
+        use std::rc::Rc;
+
+        let get_name = |obj: &Object| {
+            obj.get_property("name").unwrap().get::<String>().unwrap()
+        };
+
+        let source = Object::new(SimpleObject::get_type(), &[("name", &"source")]).unwrap();
+        let target = Object::new(SimpleObject::get_type(), &[]).unwrap();
+        let other_target = Object::new(SimpleObject::get_type(), &[]).unwrap();
+
+        let group = Rc::new(::BindingGroup::new());
+        group.bind("name", &target, "name", ::BindingFlags::SYNC_CREATE);
+
+        // Adding a binding while the group is rebinding must not panic
+        let group_clone = group.clone();
+        let other_target_clone = other_target.clone();
+        target.connect_notify_local(Some("name"), move |_, _| {
+            group_clone.bind(
+                "name",
+                &other_target_clone,
+                "name",
+                ::BindingFlags::SYNC_CREATE,
+            );
+        });
+
+        group.set_source(Some(&source));
+        assert_eq!(get_name(&target), Some("source".to_string()));
+        assert_eq!(get_name(&other_target), Some("source".to_string()));
+        assert!(format!("{:?}", group).contains("n_bindings: 2"));
+    }
+
     #[test]
     fn test_signals() {
         use std::sync::{Arc, Mutex};
@@ -650,6 +2058,165 @@ mod test {
         assert!(*name_changed_triggered.lock().unwrap());
     }
 
+    #[test]
+    fn test_declared_signals() {
+        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+
+        let value = obj
+            .emit("declared-signal", &[&"test"])
+            .unwrap()
+            .unwrap()
+            .get::<String>()
+            .unwrap();
+        assert_eq!(value, Some("test-handled".to_string()));
+    }
+
+    #[test]
+    fn test_interface_properties() {
+        use InterfaceRef;
+
+        let type_ = PropertyInterface::get_type();
+        assert!(InterfaceRef::peek(type_).is_none());
+        assert!(InterfaceRef::new(Object::static_type()).is_none());
+
+        let iface = InterfaceRef::new(type_).unwrap();
+        assert_eq!(iface.get_type(), type_);
+        assert!(InterfaceRef::peek(type_).is_some());
+
+        let pspec = iface.find_property("size").unwrap();
+        assert_eq!(pspec.get_value_type(), u32::static_type());
+        assert!(iface.find_property("no-such-property").is_none());
+
+        let properties = iface.list_properties();
+        assert_eq!(properties.len(), 1);
+        assert_eq!(properties[0].get_name(), "size");
+    }
+
+    #[test]
+    fn test_list_all_signals() {
+        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+        let signals = obj.list_all_signals();
+
+        let change_name = signals
+            .iter()
+            .find(|signal| signal.get_name() == "change-name")
+            .unwrap();
+        assert_eq!(change_name.get_itype(), SimpleObject::get_type());
+        assert_eq!(change_name.get_return_type(), String::static_type());
+        assert_eq!(change_name.get_param_types(), &[String::static_type()]);
+        assert!(change_name.get_flags().contains(SignalFlags::ACTION));
+        assert!(signals.iter().any(|signal| signal.get_name() == "declared-signal"));
+
+        let notify = signals.last().unwrap();
+        assert_eq!(notify.get_name(), "notify");
+        assert_eq!(notify.get_itype(), Object::static_type());
+        assert_eq!(::SignalInfo::query(notify.get_signal_id()).as_ref(), Some(notify));
+    }
+
+    #[test]
+    fn test_try_emit() {
+        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+
+        let value = obj
+            .try_emit("declared-signal", &["test".to_value()])
+            .unwrap()
+            .unwrap()
+            .get::<String>()
+            .unwrap();
+        assert_eq!(value, Some("test-handled".to_string()));
+
+        assert_eq!(
+            obj.try_emit("does-not-exist", &[]).err(),
+            Some(::EmitError::UnknownSignal)
+        );
+        assert_eq!(
+            obj.try_emit("declared-signal", &[]).err(),
+            Some(::EmitError::ArgumentCountMismatch {
+                expected: 1,
+                got: 0
+            })
+        );
+        assert_eq!(
+            obj.try_emit("declared-signal", &[1i32.to_value()]).err(),
+            Some(::EmitError::ArgumentTypeMismatch {
+                index: 0,
+                expected: String::static_type(),
+                got: i32::static_type(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_connect_closure() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+        let other = Object::new(SimpleObject::get_type(), &[]).unwrap();
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let count_clone = count.clone();
+        let closure = ::Closure::new(move |_args| {
+            count_clone.fetch_add(1, Ordering::Relaxed);
+            None
+        });
+
+        obj.connect_closure("name-changed", false, &closure).unwrap();
+        other.connect_closure("name-changed", false, &closure).unwrap();
+        assert!(obj.connect_closure("no-such-signal", false, &closure).is_err());
+
+        obj.set_property("name", &"a").unwrap();
+        other.set_property("name", &"b").unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 2);
+
+        let handler_id = obj.connect_closure("name-changed", true, &closure).unwrap();
+        assert!(handler_id.is_connected(&obj));
+        assert!(!handler_id.is_connected(&other));
+        obj.disconnect(handler_id);
+        assert!(!handler_id.is_connected(&obj));
+
+        let handler_id = obj
+            .connect_with_flags("name-changed", ::ConnectFlags::AFTER, &closure)
+            .unwrap();
+        obj.set_property("name", &"c").unwrap();
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+        obj.disconnect(handler_id);
+        assert!(obj
+            .connect_with_flags("name-changed", ::ConnectFlags::SWAPPED, &closure)
+            .is_err());
+    }
+
+    #[test]
+    fn test_stop_signal_emission() {
+        let obj = Object::new(SimpleObject::get_type(), &[("name", &"old-name")]).unwrap();
+        let signal_id = unsafe {
+            gobject_sys::g_signal_lookup(
+                "change-name".to_glib_none().0,
+                SimpleObject::get_type().to_glib(),
+            )
+        };
+        assert_ne!(signal_id, 0);
+
+        obj.connect("change-name", false, move |args| {
+            let obj = args[0].get::<Object>().unwrap().unwrap();
+            obj.stop_signal_emission_by_id(signal_id, None);
+            Some("stopped".to_value())
+        })
+        .unwrap();
+
+        let ret = obj
+            .emit("change-name", &[&"new-name"])
+            .unwrap()
+            .unwrap()
+            .get::<String>()
+            .unwrap();
+        assert_eq!(ret, Some("stopped".to_string()));
+        assert_eq!(
+            obj.get_property("name").unwrap().get::<&str>().unwrap(),
+            Some("old-name")
+        );
+    }
+
     #[test]
     fn test_signal_return_expected_type() {
         let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
@@ -663,6 +2230,40 @@ mod test {
         assert_eq!(value.get::<String>(), Ok(Some("return value".to_string())));
     }
 
+    #[test]
+    fn test_type_data_add_interface() {
+        let type_ = LateInterfaceObject::get_type();
+        assert!(!type_.is_a(&DummyInterface::static_type()));
+
+        let type_data = unsafe { LateInterfaceObject::type_data().as_ref() };
+        type_data.add_interface::<LateInterfaceObject, DummyInterface>().unwrap();
+        assert!(type_.is_a(&DummyInterface::static_type()));
+
+        let obj = Object::new(type_, &[]).unwrap();
+        assert!(obj.get_type().is_a(&DummyInterface::static_type()));
+
+        assert!(type_data.add_interface::<LateInterfaceObject, DummyInterface>().is_err());
+    }
+
+    #[test]
+    fn test_connect_local() {
+        use std::rc::Rc;
+
+        let obj = Object::new(SimpleObject::get_type(), &[]).unwrap();
+
+        let calls = Rc::new(RefCell::new(0));
+        let calls_clone = calls.clone();
+        obj.connect_local("create-string", false, move |_args| {
+            *calls_clone.borrow_mut() += 1;
+            Some("local value".to_value())
+        })
+        .unwrap();
+
+        let value = obj.emit("create-string", &[]).unwrap().unwrap();
+        assert_eq!(value.get::<String>(), Ok(Some("local value".to_string())));
+        assert_eq!(*calls.borrow(), 1);
+    }
+
     // Note: can't test type mismatch in signals since panics accross FFI boundaries
     // are UB. See https://github.com/gtk-rs/glib/issues/518
 
@@ -0,0 +1,216 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! Module for declaratively registering signals of subclasses.
+//!
+//! Signals are described by [`Signal`] values that are returned from
+//! [`ObjectSubclass::signals`] and are registered automatically when the class
+//! is initialized.
+//!
+//! [`Signal`]: struct.Signal.html
+//! [`ObjectSubclass::signals`]: ../types/trait.ObjectSubclass.html#method.signals
+
+use glib_sys;
+use gobject_sys;
+use std::fmt;
+use std::ptr;
+use translate::*;
+use {Closure, SignalFlags, Type, Value};
+
+use super::types::{SignalClassHandlerToken, SignalInvocationHint};
+
+type ClassHandler =
+    Box<dyn Fn(&SignalClassHandlerToken, &[Value]) -> Option<Value> + Send + Sync + 'static>;
+type Accumulator =
+    Box<dyn Fn(&SignalInvocationHint, &mut Value, &Value) -> bool + Send + Sync + 'static>;
+
+/// Declaration of a signal of an object subclass.
+///
+/// This is usually stored in a `static` and returned from
+/// [`ObjectSubclass::signals`], e.g.
+///
+/// ```ignore
+/// static SIGNALS: Lazy<Vec<Signal>> = Lazy::new(|| {
+///     vec![Signal::builder("name-changed", &[String::static_type()], Type::Unit)
+///         .flags(SignalFlags::RUN_LAST)
+///         .build()]
+/// });
+/// ```
+///
+/// [`ObjectSubclass::signals`]: ../types/trait.ObjectSubclass.html#method.signals
+pub struct Signal {
+    name: String,
+    flags: SignalFlags,
+    param_types: Vec<Type>,
+    return_type: Type,
+    class_handler: Option<ClassHandler>,
+    accumulator: Option<Accumulator>,
+}
+
+impl Signal {
+    /// Create a new builder for a signal with the given name, parameter types and return type.
+    pub fn builder(name: &str, param_types: &[Type], return_type: Type) -> SignalBuilder {
+        SignalBuilder {
+            name: String::from(name),
+            flags: SignalFlags::empty(),
+            param_types: param_types.to_vec(),
+            return_type,
+            class_handler: None,
+            accumulator: None,
+        }
+    }
+
+    /// Name of the signal.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Flags of the signal.
+    pub fn flags(&self) -> SignalFlags {
+        self.flags
+    }
+
+    /// Parameter types of the signal.
+    pub fn param_types(&self) -> &[Type] {
+        &self.param_types
+    }
+
+    /// Return type of the signal.
+    pub fn return_type(&self) -> Type {
+        self.return_type
+    }
+
+    pub(crate) unsafe fn register(&'static self, type_: glib_sys::GType) {
+        let param_types = self
+            .param_types
+            .iter()
+            .map(ToGlib::to_glib)
+            .collect::<Vec<_>>();
+
+        let class_handler = self.class_handler.as_ref().map(|class_handler| {
+            Closure::new(move |values| {
+                let instance = gobject_sys::g_value_get_object(values[0].to_glib_none().0);
+                class_handler(&SignalClassHandlerToken(instance as *mut _), values)
+            })
+        });
+
+        unsafe extern "C" fn accumulator_trampoline(
+            ihint: *mut gobject_sys::GSignalInvocationHint,
+            return_accu: *mut gobject_sys::GValue,
+            handler_return: *const gobject_sys::GValue,
+            data: glib_sys::gpointer,
+        ) -> glib_sys::gboolean {
+            let accumulator = &*(data as *const Accumulator);
+            accumulator(
+                &SignalInvocationHint(*ihint),
+                &mut *(return_accu as *mut Value),
+                &*(handler_return as *const Value),
+            )
+            .to_glib()
+        }
+
+        let (accumulator, accumulator_data): (gobject_sys::GSignalAccumulator, _) =
+            match self.accumulator {
+                Some(ref accumulator) => (
+                    Some(accumulator_trampoline as _),
+                    accumulator as *const Accumulator as glib_sys::gpointer,
+                ),
+                None => (None, ptr::null_mut()),
+            };
+
+        gobject_sys::g_signal_newv(
+            self.name.to_glib_none().0,
+            type_,
+            self.flags.to_glib(),
+            class_handler.to_glib_none().0,
+            accumulator,
+            accumulator_data,
+            None,
+            self.return_type.to_glib(),
+            param_types.len() as u32,
+            param_types.as_ptr() as *mut _,
+        );
+    }
+}
+
+impl fmt::Debug for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Signal")
+            .field("name", &self.name)
+            .field("flags", &self.flags)
+            .field("param_types", &self.param_types)
+            .field("return_type", &self.return_type)
+            .field("class_handler", &self.class_handler.is_some())
+            .field("accumulator", &self.accumulator.is_some())
+            .finish()
+    }
+}
+
+/// Builder for [`Signal`].
+///
+/// [`Signal`]: struct.Signal.html
+pub struct SignalBuilder {
+    name: String,
+    flags: SignalFlags,
+    param_types: Vec<Type>,
+    return_type: Type,
+    class_handler: Option<ClassHandler>,
+    accumulator: Option<Accumulator>,
+}
+
+impl SignalBuilder {
+    /// Set the flags of the signal.
+    pub fn flags(self, flags: SignalFlags) -> Self {
+        Self { flags, ..self }
+    }
+
+    /// Set the class handler of the signal.
+    pub fn class_handler<
+        F: Fn(&SignalClassHandlerToken, &[Value]) -> Option<Value> + Send + Sync + 'static,
+    >(
+        self,
+        func: F,
+    ) -> Self {
+        Self {
+            class_handler: Some(Box::new(func)),
+            ..self
+        }
+    }
+
+    /// Set the accumulator of the signal.
+    pub fn accumulator<
+        F: Fn(&SignalInvocationHint, &mut Value, &Value) -> bool + Send + Sync + 'static,
+    >(
+        self,
+        func: F,
+    ) -> Self {
+        Self {
+            accumulator: Some(Box::new(func)),
+            ..self
+        }
+    }
+
+    /// Build the signal declaration.
+    pub fn build(self) -> Signal {
+        Signal {
+            name: self.name,
+            flags: self.flags,
+            param_types: self.param_types,
+            return_type: self.return_type,
+            class_handler: self.class_handler,
+            accumulator: self.accumulator,
+        }
+    }
+}
+
+impl fmt::Debug for SignalBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SignalBuilder")
+            .field("name", &self.name)
+            .field("flags", &self.flags)
+            .field("param_types", &self.param_types)
+            .field("return_type", &self.return_type)
+            .finish()
+    }
+}
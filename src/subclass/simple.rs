@@ -8,6 +8,8 @@
 
 use super::prelude::*;
 use object::ObjectType;
+use ObjectClass;
+use Type;
 
 use std::ops;
 
@@ -32,6 +34,17 @@ unsafe impl<T: ObjectSubclass> super::types::ClassStruct for ClassStruct<T> {
     type Type = T;
 }
 
+impl<T: ObjectSubclass> ClassStruct<T> {
+    /// Checks if the class has a property called `property_name`.
+    ///
+    /// If `type_` is not `None`, this additionally checks that the property
+    /// is of the given type. This is mostly useful for testing subclasses.
+    pub fn has_property(&self, property_name: &str, type_: Option<Type>) -> bool {
+        let klass = unsafe { &*(self as *const Self as *const ObjectClass) };
+        klass.has_property(property_name, type_).is_ok()
+    }
+}
+
 impl<T: ObjectSubclass> ops::Deref for ClassStruct<T> {
     type Target = <<T as ObjectSubclass>::ParentType as ObjectType>::RustClassType;
 
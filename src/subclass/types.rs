@@ -5,15 +5,17 @@
 //! Module that contains the basic infrastructure for subclassing `GObject`.
 
 use super::object::ObjectImpl;
+use super::signal::Signal;
 use glib_sys;
 use gobject_sys;
 use object::{ObjectExt, ObjectType};
+use std::any::TypeId;
 use std::fmt;
 use std::marker;
 use std::mem;
 use std::ptr;
 use translate::*;
-use {Closure, IsA, IsClassFor, SignalFlags, StaticType, Type, Value};
+use {BoolError, Closure, IsA, IsClassFor, SignalFlags, StaticType, Type, Value};
 
 /// A newly registered `glib::Type` that is currently still being initialized.
 ///
@@ -38,6 +40,49 @@ impl<T: ObjectSubclass> InitializingType<T> {
             );
         }
     }
+
+    /// Adds class private data of type `P` to the type.
+    ///
+    /// The private data is initialized with `P::default()` right before
+    /// `class_init` is called and can be accessed via
+    /// [`ClassStruct::class_private`] afterwards.
+    ///
+    /// Subclasses of this type get a bitwise copy of the parent class' private
+    /// data, so `P` should usually be plain data.
+    ///
+    /// Panics if `P` is zero-sized or needs a bigger alignment than GLib
+    /// guarantees for class private data, which is two pointers.
+    ///
+    /// [`ClassStruct::class_private`]: trait.ClassStruct.html#method.class_private
+    pub fn add_class_private<P: Default + 'static>(&mut self) {
+        unsafe extern "C" fn init<P: Default>(ptr: glib_sys::gpointer) {
+            ptr::write(ptr as *mut P, P::default());
+        }
+
+        assert_ne!(mem::size_of::<P>(), 0, "Class private data can't be zero-sized");
+        assert!(
+            mem::align_of::<P>() <= 2 * mem::size_of::<usize>(),
+            "Class private data can't be aligned to more than two pointers"
+        );
+
+        unsafe {
+            let mut data = T::type_data();
+            assert!(
+                data.as_ref().class_private.is_none(),
+                "Class private data already added"
+            );
+
+            gobject_sys::g_type_add_class_private(self.0.to_glib(), mem::size_of::<P>());
+            (*data.as_mut()).class_private = Some((TypeId::of::<P>(), init::<P>));
+            // Remember which type added the private data so that it can also be found from
+            // the class structs of subclasses
+            gobject_sys::g_type_set_qdata(
+                self.0.to_glib(),
+                class_private_quark().to_glib(),
+                data.as_ptr() as glib_sys::gpointer,
+            );
+        }
+    }
 }
 
 impl<T> ToGlib for InitializingType<T> {
@@ -65,6 +110,10 @@ pub unsafe trait InstanceStruct: Sized + 'static {
     /// Returns the implementation for from this instance struct, that
     /// is the implementor of [`ObjectImpl`] or subtraits.
     ///
+    /// The implementation is stored as the instance private data of the type,
+    /// which is registered via `g_type_add_instance_private()`, so any
+    /// additional per-instance data should be stored as fields of it.
+    ///
     /// [`ObjectImpl`]: ../object/trait.ObjectImpl.html
     fn get_impl(&self) -> &Self::Type {
         unsafe {
@@ -78,6 +127,36 @@ pub unsafe trait InstanceStruct: Sized + 'static {
         }
     }
 
+    /// Returns the implementation for the instance struct at `ptr`.
+    ///
+    /// This is the same as `(*ptr).get_impl()` and is useful in trampolines
+    /// that are called with a raw instance pointer.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid, initialized instance of `Self::Type` and
+    /// the instance must stay alive for the lifetime `'a`.
+    unsafe fn downcast_impl<'a>(ptr: *mut Self) -> &'a Self::Type {
+        (*ptr).get_impl()
+    }
+
+    /// Returns the implementation for the instance struct at `ptr` mutably.
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`downcast_impl`], and additionally no other references to
+    /// the implementation may exist for the lifetime `'a`.
+    ///
+    /// [`downcast_impl`]: #method.downcast_impl
+    unsafe fn downcast_impl_mut<'a>(ptr: *mut Self) -> &'a mut Self::Type {
+        let data = Self::Type::type_data();
+        let private_offset = data.as_ref().private_offset;
+        let priv_ptr = (ptr as *mut u8).offset(private_offset);
+        let imp = priv_ptr as *mut Option<Self::Type>;
+
+        (*imp).as_mut().expect("No private struct")
+    }
+
     /// Returns the class struct for this specific instance.
     fn get_class(&self) -> &<Self::Type as ObjectSubclass>::Class {
         unsafe { &**(self as *const _ as *const *const <Self::Type as ObjectSubclass>::Class) }
@@ -112,6 +191,51 @@ pub unsafe trait ClassStruct: Sized + 'static {
             base.override_vfuncs();
         }
     }
+
+    /// Returns the class private data added via [`InitializingType::add_class_private`].
+    ///
+    /// Panics if no class private data of type `P` was added.
+    ///
+    /// [`InitializingType::add_class_private`]: struct.InitializingType.html#method.add_class_private
+    fn class_private<P: 'static>(&self) -> &P {
+        unsafe { &*(class_private_ptr::<Self, P>(self as *const Self as *mut Self) as *const P) }
+    }
+
+    /// Returns the mutable class private data added via
+    /// [`InitializingType::add_class_private`].
+    ///
+    /// Panics if no class private data of type `P` was added.
+    ///
+    /// [`InitializingType::add_class_private`]: struct.InitializingType.html#method.add_class_private
+    fn class_private_mut<P: 'static>(&mut self) -> &mut P {
+        unsafe { &mut *(class_private_ptr::<Self, P>(self as *mut Self) as *mut P) }
+    }
+}
+
+fn class_private_quark() -> ::Quark {
+    ::Quark::from_string("glib-rs-class-private")
+}
+
+unsafe fn class_private_ptr<C: ClassStruct, P: 'static>(klass: *mut C) -> glib_sys::gpointer {
+    // The private data might have been added by any of the parent types, so walk up the
+    // hierarchy until a type that added private data of type `P` is found
+    let quark = class_private_quark().to_glib();
+    let mut type_: Option<Type> =
+        Some(from_glib((*(klass as *mut gobject_sys::GTypeClass)).g_type));
+    while let Some(t) = type_ {
+        let data = gobject_sys::g_type_get_qdata(t.to_glib(), quark) as *const TypeData;
+        if let Some(&(type_id, _)) = data.as_ref().and_then(|data| data.class_private.as_ref()) {
+            if type_id == TypeId::of::<P>() {
+                return gobject_sys::g_type_class_get_private(
+                    klass as *mut gobject_sys::GTypeClass,
+                    t.to_glib(),
+                );
+            }
+        }
+        type_ = t.parent();
+    }
+
+    panic!("No class private data of this type added");
 }
 
 /// Trait for subclassable class structs.
@@ -138,6 +262,8 @@ pub struct TypeData {
     pub interface_data: *const Vec<(glib_sys::GType, glib_sys::gpointer)>,
     #[doc(hidden)]
     pub private_offset: isize,
+    #[doc(hidden)]
+    pub class_private: Option<(TypeId, unsafe extern "C" fn(glib_sys::gpointer))>,
 }
 
 unsafe impl Send for TypeData {}
@@ -157,6 +283,22 @@ impl TypeData {
         self.parent_class
     }
 
+    /// Returns the class struct of the type.
+    ///
+    /// Panics if `T` is not the subclass this type data belongs to or if the
+    /// class was not initialized yet.
+    pub fn get_class<T: ObjectSubclass>(&self) -> &T::Class {
+        assert!(ptr::eq(self, T::type_data().as_ptr()));
+        assert_ne!(self.type_, Type::Invalid);
+
+        unsafe {
+            let klass = gobject_sys::g_type_class_peek(self.type_.to_glib());
+            assert!(!klass.is_null(), "Class not initialized yet");
+
+            &*(klass as *const T::Class)
+        }
+    }
+
     /// Returns a pointer to the interface implementation specific data.
     ///
     /// This is used for interface implementations to store additional data.
@@ -181,6 +323,29 @@ impl TypeData {
     pub fn get_private_offset(&self) -> isize {
         self.private_offset
     }
+
+    /// Adds an interface implementation for `I` to the already registered type.
+    ///
+    /// This is the same as [`InitializingType::add_interface`] but can be called
+    /// after `type_init`, e.g. from a `TypeModule` that loads interface
+    /// implementations later. Fails if the type already implements `I`.
+    ///
+    /// Panics if `T` is not the subclass this type data belongs to or if the
+    /// type was not registered yet.
+    ///
+    /// [`InitializingType::add_interface`]: struct.InitializingType.html#method.add_interface
+    pub fn add_interface<T: ObjectSubclass, I: IsImplementable<T>>(&self) -> Result<(), BoolError> {
+        assert!(ptr::eq(self, T::type_data().as_ptr()));
+        assert_ne!(self.type_, Type::Invalid);
+
+        if self.type_.is_a(&I::static_type()) {
+            return Err(glib_bool_error!("Type already implements the interface"));
+        }
+
+        InitializingType::<T>(self.type_, marker::PhantomData).add_interface::<I>();
+
+        Ok(())
+    }
 }
 
 #[macro_export]
@@ -195,6 +360,7 @@ macro_rules! glib_object_subclass {
                 parent_class: ::std::ptr::null_mut(),
                 interface_data: ::std::ptr::null_mut(),
                 private_offset: 0,
+                class_private: None,
             };
 
             unsafe { ::std::ptr::NonNull::new_unchecked(&mut DATA) }
@@ -204,7 +370,10 @@ macro_rules! glib_object_subclass {
             static ONCE: ::std::sync::Once = ::std::sync::Once::new();
 
             ONCE.call_once(|| {
-                $crate::subclass::register_type::<Self>();
+                // Dynamic types are already registered by `register_dynamic_type()`
+                if unsafe { Self::type_data().as_ref().get_type() } == $crate::Type::Invalid {
+                    $crate::subclass::register_type::<Self>();
+                }
             });
 
             unsafe {
@@ -322,6 +491,23 @@ pub trait ObjectSubclass: ObjectImpl + Sized + 'static {
         }
     }
 
+    /// Returns the class struct of the subclass.
+    ///
+    /// The class is initialized if that did not happen yet and is never freed again.
+    fn class() -> &'static Self::Class {
+        unsafe {
+            let type_ = Self::get_type().to_glib();
+            let mut klass = gobject_sys::g_type_class_peek_static(type_);
+            if klass.is_null() {
+                // The reference is never released, static types are kept forever anyway
+                klass = gobject_sys::g_type_class_ref(type_);
+            }
+            assert!(!klass.is_null());
+
+            &*(klass as *const Self::Class)
+        }
+    }
+
     /// Additional type initialization.
     ///
     /// This is called right after the type was registered and allows
@@ -341,6 +527,17 @@ pub trait ObjectSubclass: ObjectImpl + Sized + 'static {
     /// Optional
     fn class_init(_klass: &mut Self::Class) {}
 
+    /// Signals of the subclass.
+    ///
+    /// The returned signals are registered right before `class_init` is
+    /// called, so they can be declared in one place together with the
+    /// properties instead of being added imperatively from `class_init`.
+    ///
+    /// Optional
+    fn signals() -> &'static [Signal] {
+        &[]
+    }
+
     /// Constructor.
     ///
     /// This is called during object instantiation before further subclasses
@@ -367,6 +564,27 @@ pub trait ObjectSubclass: ObjectImpl + Sized + 'static {
     }
 }
 
+/// Extension trait for getting the implementation struct of a subclass from
+/// an object reference.
+pub trait ObjectSubclassIsExt: ObjectType {
+    /// Returns the implementation of the subclass `T` of this object.
+    ///
+    /// This is the same as `T::from_instance(self)` and panics if the object
+    /// is not an instance of `T`.
+    fn imp<T: ObjectSubclass>(&self) -> &T
+    where
+        Self: IsA<T::ParentType>;
+}
+
+impl<O: ObjectType> ObjectSubclassIsExt for O {
+    fn imp<T: ObjectSubclass>(&self) -> &T
+    where
+        Self: IsA<T::ParentType>,
+    {
+        T::from_instance(self)
+    }
+}
+
 unsafe extern "C" fn class_init<T: ObjectSubclass>(
     klass: glib_sys::gpointer,
     _klass_data: glib_sys::gpointer,
@@ -378,7 +596,15 @@ unsafe extern "C" fn class_init<T: ObjectSubclass>(
     // We have to update the private struct offset once the class is actually
     // being initialized.
     {
-        let mut private_offset = data.as_ref().private_offset as i32;
+        // Dynamic types can't use g_type_add_instance_private() and have to add the private
+        // struct every time their class is initialized again. Passing its size instead of an
+        // offset does exactly that.
+        let plugin = gobject_sys::g_type_get_plugin(data.as_ref().get_type().to_glib());
+        let mut private_offset = if plugin.is_null() {
+            data.as_ref().private_offset as i32
+        } else {
+            mem::size_of::<Option<T>>() as i32
+        };
         gobject_sys::g_type_class_adjust_private_offset(klass, &mut private_offset);
         (*data.as_mut()).private_offset = private_offset as isize;
     }
@@ -403,6 +629,24 @@ unsafe extern "C" fn class_init<T: ObjectSubclass>(
         (*data.as_mut()).parent_class = parent_class as glib_sys::gpointer;
 
         klass.override_vfuncs();
+
+        let type_ = data.as_ref().get_type().to_glib();
+
+        if let Some((_, init)) = data.as_ref().class_private {
+            init(gobject_sys::g_type_class_get_private(
+                klass as *mut _ as *mut gobject_sys::GTypeClass,
+                type_,
+            ));
+        }
+        for signal in T::signals() {
+            signal.register(type_);
+        }
+
+        super::object::install_property_defs(
+            klass as *mut _ as *mut gobject_sys::GObjectClass,
+            T::properties(),
+        );
+
         T::class_init(klass);
     }
 }
@@ -473,6 +717,8 @@ where
             value_table: ptr::null(),
         };
 
+        debug_assert!(::TypeName::is_valid(T::NAME), "Invalid type name '{}'", T::NAME);
+
         let type_name = CString::new(T::NAME).unwrap();
         if gobject_sys::g_type_from_name(type_name.as_ptr()) != gobject_sys::G_TYPE_INVALID {
             panic!(
@@ -504,6 +750,69 @@ where
     }
 }
 
+/// Register a dynamic `glib::Type` ID for `T` with `type_module`.
+///
+/// Unlike types registered with [`register_type`], the class of a dynamic type is finalized when
+/// the module is unloaded and initialized again once it is loaded and used next. This has to be
+/// called every time the module is loaded, usually from its `load` implementation.
+///
+/// Afterwards `T::get_type()` returns the registered type.
+///
+/// [`register_type`]: fn.register_type.html
+pub fn register_dynamic_type<T: ObjectSubclass>(type_module: &::TypeModule) -> Type
+where
+    <<T as ObjectSubclass>::ParentType as ObjectType>::RustClassType: IsSubclassable<T>,
+{
+    unsafe {
+        use std::ffi::CString;
+
+        let type_info = gobject_sys::GTypeInfo {
+            class_size: mem::size_of::<T::Class>() as u16,
+            base_init: None,
+            base_finalize: None,
+            class_init: Some(class_init::<T>),
+            class_finalize: None,
+            class_data: ptr::null_mut(),
+            instance_size: mem::size_of::<T::Instance>() as u16,
+            n_preallocs: 0,
+            instance_init: Some(instance_init::<T>),
+            value_table: ptr::null(),
+        };
+
+        debug_assert!(::TypeName::is_valid(T::NAME), "Invalid type name '{}'", T::NAME);
+
+        let mut data = T::type_data();
+        let previous_type = data.as_ref().get_type();
+
+        let type_name = CString::new(T::NAME).unwrap();
+        let type_: Type = from_glib(gobject_sys::g_type_module_register_type(
+            type_module.to_glib_none().0,
+            <T::ParentType as StaticType>::static_type().to_glib(),
+            type_name.as_ptr(),
+            &type_info,
+            if T::ABSTRACT {
+                gobject_sys::G_TYPE_FLAG_ABSTRACT
+            } else {
+                0
+            },
+        ));
+        assert_ne!(type_, Type::Invalid, "Failed to register type {}", T::NAME);
+        assert!(
+            previous_type == Type::Invalid || previous_type == type_,
+            "Type {} has already been registered",
+            T::NAME
+        );
+
+        (*data.as_mut()).type_ = type_;
+
+        if previous_type == Type::Invalid {
+            T::type_init(&mut InitializingType::<T>(type_, marker::PhantomData));
+        }
+
+        type_
+    }
+}
+
 pub(crate) unsafe fn add_signal(
     type_: glib_sys::GType,
     name: &str,
@@ -528,7 +837,7 @@ pub(crate) unsafe fn add_signal(
 }
 
 #[repr(C)]
-pub struct SignalInvocationHint(gobject_sys::GSignalInvocationHint);
+pub struct SignalInvocationHint(pub(crate) gobject_sys::GSignalInvocationHint);
 
 impl SignalInvocationHint {
     pub fn detail(&self) -> ::Quark {
@@ -594,7 +903,7 @@ pub(crate) unsafe fn add_signal_with_accumulator<F>(
     );
 }
 
-pub struct SignalClassHandlerToken(*mut gobject_sys::GTypeInstance);
+pub struct SignalClassHandlerToken(pub(crate) *mut gobject_sys::GTypeInstance);
 
 impl fmt::Debug for SignalClassHandlerToken {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
@@ -614,17 +923,29 @@ pub(crate) unsafe fn add_signal_with_class_handler<F>(
 ) where
     F: Fn(&SignalClassHandlerToken, &[Value]) -> Option<Value> + Send + Sync + 'static,
 {
-    let arg_types = arg_types.iter().map(ToGlib::to_glib).collect::<Vec<_>>();
     let class_handler = Closure::new(move |values| {
         let instance = gobject_sys::g_value_get_object(values[0].to_glib_none().0);
         class_handler(&SignalClassHandlerToken(instance as *mut _), values)
     });
 
+    add_signal_with_class_closure(type_, name, flags, arg_types, ret_type, &class_handler);
+}
+
+pub(crate) unsafe fn add_signal_with_class_closure(
+    type_: glib_sys::GType,
+    name: &str,
+    flags: SignalFlags,
+    arg_types: &[Type],
+    ret_type: Type,
+    class_closure: &Closure,
+) {
+    let arg_types = arg_types.iter().map(ToGlib::to_glib).collect::<Vec<_>>();
+
     gobject_sys::g_signal_newv(
         name.to_glib_none().0,
         type_,
         flags.to_glib(),
-        class_handler.to_glib_none().0,
+        class_closure.to_glib_none().0,
         None,
         ptr::null_mut(),
         None,
@@ -697,6 +1018,14 @@ pub(crate) unsafe fn signal_override_class_handler<F>(
         class_handler(&SignalClassHandlerToken(instance as *mut _), values)
     });
 
+    signal_override_class_closure(name, type_, &class_handler);
+}
+
+pub(crate) unsafe fn signal_override_class_closure(
+    name: &str,
+    type_: glib_sys::GType,
+    class_closure: &Closure,
+) {
     let mut signal_id = 0;
     let found: bool = from_glib(gobject_sys::g_signal_parse_name(
         name.to_glib_none().0,
@@ -710,7 +1039,7 @@ pub(crate) unsafe fn signal_override_class_handler<F>(
         panic!("Signal '{}' not found", name);
     }
 
-    gobject_sys::g_signal_override_class_closure(signal_id, type_, class_handler.to_glib_none().0);
+    gobject_sys::g_signal_override_class_closure(signal_id, type_, class_closure.to_glib_none().0);
 }
 
 pub(crate) unsafe fn signal_chain_from_overridden(
@@ -0,0 +1,130 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use glib_sys;
+use libc;
+use std::fs;
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::{Path, PathBuf};
+use std::ptr;
+use translate::*;
+use Error;
+
+/// A temporary file that is removed again when dropped.
+#[derive(Debug)]
+pub struct TempFile {
+    fd: i32,
+    path: PathBuf,
+}
+
+impl TempFile {
+    /// Creates and opens a new temporary file in the directory returned by
+    /// `get_tmp_dir()`.
+    ///
+    /// `template` is the basename of the file and must contain `XXXXXX`,
+    /// which is replaced by a unique string.
+    pub fn new(template: &str) -> Result<TempFile, Error> {
+        unsafe {
+            let mut name = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let fd = glib_sys::g_file_open_tmp(template.to_glib_none().0, &mut name, &mut error);
+            if error.is_null() {
+                Ok(TempFile {
+                    fd,
+                    path: from_glib_full(name),
+                })
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for TempFile {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// A temporary directory that is removed again, including its contents,
+/// when dropped.
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates a new temporary directory in the directory returned by
+    /// `get_tmp_dir()`.
+    ///
+    /// `template` is the basename of the directory and must contain
+    /// `XXXXXX`, which is replaced by a unique string.
+    pub fn new(template: &str) -> Result<TempDir, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = glib_sys::g_dir_make_tmp(template.to_glib_none().0, &mut error);
+            if error.is_null() {
+                Ok(TempDir {
+                    path: from_glib_full(ret),
+                })
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temp_file() {
+        let file = TempFile::new("glib-test-XXXXXX").unwrap();
+        let path = file.path().to_owned();
+        assert!(path.is_file());
+        assert!(path.file_name().unwrap().to_str().unwrap().starts_with("glib-test-"));
+
+        drop(file);
+        assert!(!path.exists());
+
+        assert!(TempFile::new("glib-test").is_err());
+    }
+
+    #[test]
+    fn test_temp_dir() {
+        let dir = TempDir::new("glib-test-XXXXXX").unwrap();
+        let path = dir.path().to_owned();
+        assert!(path.is_dir());
+        ::file_set_contents(path.join("file"), b"contents").unwrap();
+
+        drop(dir);
+        assert!(!path.exists());
+    }
+}
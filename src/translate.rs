@@ -48,6 +48,7 @@
 //! ```
 
 use glib_sys;
+use gobject_sys;
 use libc::{c_char, size_t};
 use std::char;
 use std::cmp::Ordering;
@@ -60,6 +61,26 @@ use std::os::unix::prelude::*;
 use std::path::{Path, PathBuf};
 use std::ptr;
 
+// Compile-time checks for the sizes the translations and pointer casts in this crate rely on.
+// A mismatch means the FFI definitions don't match the platform, e.g. when cross-compiling
+// with bindings generated for a different target.
+macro_rules! assert_same_size {
+    ($a:ty, $b:ty) => {
+        const _: [(); 0] = [(); (mem::size_of::<$a>() != mem::size_of::<$b>()) as usize];
+    };
+}
+
+// `GType` is a `gsize` in C and is converted from and to `usize` for `Type::Other`.
+assert_same_size!(glib_sys::GType, usize);
+// `bool` is translated from and to `gboolean`, which is a C `int`.
+assert_same_size!(glib_sys::gboolean, i32);
+// `Value` and `SendValue` pointers are cast from and to `GValue` pointers, e.g. for signal
+// arguments, and `&[Value]` slices are passed as `GValue` arrays.
+assert_same_size!(::Value, gobject_sys::GValue);
+assert_same_size!(::SendValue, gobject_sys::GValue);
+// Class struct pointers of instances are cast to `&ObjectClass` and back.
+assert_same_size!(::ObjectClass, gobject_sys::GObjectClass);
+
 /// A pointer
 pub trait Ptr: Copy + 'static {
     fn is_null(&self) -> bool;
@@ -7,11 +7,12 @@
 use glib_sys;
 use gobject_sys;
 use translate::{
-    from_glib, from_glib_none, FromGlib, FromGlibContainerAsVec, ToGlib, ToGlibContainerFromSlice,
-    ToGlibPtr, ToGlibPtrMut,
+    from_glib, FromGlib, FromGlibContainerAsVec, ToGlib, ToGlibContainerFromSlice, ToGlibPtr,
+    ToGlibPtrMut,
 };
 use value::{FromValue, FromValueOptional, SetValue, Value};
 
+use std::ffi::CStr;
 use std::fmt;
 use std::mem;
 use std::ptr;
@@ -68,10 +69,14 @@ pub enum Type {
 }
 
 impl Type {
-    pub fn name(&self) -> String {
+    pub fn name(&self) -> &'static str {
         match self {
-            Type::Invalid => "<invalid>".to_string(),
-            _ => unsafe { from_glib_none(gobject_sys::g_type_name(self.to_glib())) },
+            Type::Invalid => "<invalid>",
+            _ => unsafe {
+                let ptr = gobject_sys::g_type_name(self.to_glib());
+                assert!(!ptr.is_null());
+                CStr::from_ptr(ptr).to_str().unwrap()
+            },
         }
     }
 
@@ -127,6 +132,17 @@ impl Type {
         }
     }
 
+    /// Ensures that the type is registered with the type system.
+    ///
+    /// Getting a type usually registers it already, but this prevents the compiler from
+    /// optimizing away calls, e.g. for having the type available by name via
+    /// [`from_name`](#method.from_name) afterwards.
+    pub fn ensure(&self) {
+        unsafe {
+            gobject_sys::g_type_ensure(self.to_glib());
+        }
+    }
+
     pub fn from_name<'a, P: Into<&'a str>>(name: P) -> Option<Self> {
         unsafe {
             let type_ = gobject_sys::g_type_from_name(name.into().to_glib_none().0);
@@ -141,13 +157,111 @@ impl Type {
 
 impl fmt::Debug for Type {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(&self.name())
+        f.write_str(self.name())
     }
 }
 
 impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(&self.name())
+        f.write_str(self.name())
+    }
+}
+
+/// Size information about a classed type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TypeInfo {
+    type_: Type,
+    type_name: &'static str,
+    class_size: u32,
+    instance_size: u32,
+}
+
+impl TypeInfo {
+    /// Queries the information about `type_`.
+    ///
+    /// Returns `None` if `type_` is invalid or not a classed type.
+    pub fn query(type_: Type) -> Option<TypeInfo> {
+        unsafe {
+            let mut query = mem::MaybeUninit::zeroed();
+            gobject_sys::g_type_query(type_.to_glib(), query.as_mut_ptr());
+            let query: gobject_sys::GTypeQuery = query.assume_init();
+
+            if query.type_ == gobject_sys::G_TYPE_INVALID {
+                return None;
+            }
+
+            Some(TypeInfo {
+                type_: from_glib(query.type_),
+                type_name: CStr::from_ptr(query.type_name).to_str().unwrap(),
+                class_size: query.class_size,
+                instance_size: query.instance_size,
+            })
+        }
+    }
+
+    pub fn type_(&self) -> Type {
+        self.type_
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the size of the class struct in bytes.
+    pub fn class_size(&self) -> u32 {
+        self.class_size
+    }
+
+    /// Returns the size of the instance struct in bytes, or 0 for non-instantiatable types.
+    pub fn instance_size(&self) -> u32 {
+        self.instance_size
+    }
+}
+
+/// A validated GLib type name.
+///
+/// Type names must be at least three and at most 200 characters long, start with
+/// a letter or `_` and otherwise only contain letters, digits, `-`, `_` and `+`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TypeName(&'static str);
+
+impl TypeName {
+    /// Creates a new type name.
+    ///
+    /// In debug builds this panics if `name` is not a valid GLib type name.
+    pub fn new(name: &'static str) -> TypeName {
+        debug_assert!(TypeName::is_valid(name), "Invalid type name '{}'", name);
+        TypeName(name)
+    }
+
+    /// Checks whether `name` is a valid GLib type name.
+    pub fn is_valid(name: &str) -> bool {
+        let mut chars = name.chars();
+        let first_valid = match chars.next() {
+            Some(c) => c.is_ascii_alphabetic() || c == '_',
+            None => false,
+        };
+
+        first_valid
+            && name.len() >= 3
+            && name.len() <= 200
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '+')
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Debug for TypeName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl fmt::Display for TypeName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.0)
     }
 }
 
@@ -390,4 +504,51 @@ mod tests {
         assert_eq!(invalid.interface_prerequisites(), vec![]);
         dbg!(&invalid);
     }
+
+    #[test]
+    fn type_info() {
+        let info = TypeInfo::query(::Object::static_type()).unwrap();
+        assert_eq!(info.type_(), ::Object::static_type());
+        assert_eq!(info.type_name(), "GObject");
+        assert_eq!(info.class_size() as usize, mem::size_of::<gobject_sys::GObjectClass>());
+        assert_eq!(info.instance_size() as usize, mem::size_of::<gobject_sys::GObject>());
+
+        assert_eq!(TypeInfo::query(Type::Invalid), None);
+        assert_eq!(TypeInfo::query(Type::I32), None);
+    }
+
+    #[test]
+    fn hierarchy() {
+        let object = ::Object::static_type();
+        let initially_unowned = ::InitiallyUnowned::static_type();
+
+        assert_eq!(object.name(), "GObject");
+        assert_eq!(initially_unowned.name(), "GInitiallyUnowned");
+        assert!(initially_unowned.is_a(&object));
+        assert!(!object.is_a(&initially_unowned));
+        assert_eq!(initially_unowned.parent(), Some(object));
+        assert_eq!(object.parent(), None);
+        assert!(object.children().contains(&initially_unowned));
+        assert_eq!(object.interfaces(), vec![]);
+    }
+
+    #[test]
+    fn ensure() {
+        let type_ = ::Object::static_type();
+        type_.ensure();
+        assert_eq!(Type::from_name("GObject"), Some(type_));
+    }
+
+    #[test]
+    fn type_name() {
+        assert!(TypeName::is_valid("GObject"));
+        assert!(TypeName::is_valid("_My-Type+1"));
+        assert!(!TypeName::is_valid(""));
+        assert!(!TypeName::is_valid("Ab"));
+        assert!(!TypeName::is_valid("1Type"));
+        assert!(!TypeName::is_valid("My Type"));
+        assert!(!TypeName::is_valid(&"A".repeat(201)));
+
+        assert_eq!(TypeName::new("GObject").as_str(), "GObject");
+    }
 }
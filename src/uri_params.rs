@@ -0,0 +1,178 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use libc::c_char;
+use std::borrow::Cow;
+use std::ffi::CStr;
+use std::str;
+use translate::*;
+
+bitflags! {
+    pub struct UriParamsFlags: u32 {
+        const NONE = 0;
+        const CASE_INSENSITIVE = 1;
+        const WWW_FORM = 2;
+        const PARSE_RELAXED = 4;
+    }
+}
+
+/// Iterator over the `key=value` pairs of a URI query string.
+///
+/// The pairs are separated by any of the characters in `separators`, and keys
+/// and values are percent-decoded. Parameters without `=` have a `None` value.
+///
+/// With `UriParamsFlags::WWW_FORM` a `+` is decoded as a space. Keys or values
+/// with invalid percent-encoding or that don't decode to valid UTF-8 end the
+/// iteration, unless `UriParamsFlags::PARSE_RELAXED` is given in which case
+/// they are returned undecoded or with invalid UTF-8 replaced by `U+FFFD`
+/// respectively.
+///
+/// Like with `g_uri_params_iter_next()`, keys are returned as they are also with
+/// `UriParamsFlags::CASE_INSENSITIVE`. Callers that treat keys case-insensitively
+/// have to compare them accordingly, e.g. via `str::eq_ignore_ascii_case()`.
+#[derive(Debug)]
+pub struct UriParamsIter<'a> {
+    params: &'a str,
+    separators: &'a str,
+    flags: UriParamsFlags,
+}
+
+impl<'a> UriParamsIter<'a> {
+    pub fn new(params: &'a str, separators: &'a str, flags: UriParamsFlags) -> Self {
+        UriParamsIter {
+            params,
+            separators,
+            flags,
+        }
+    }
+
+    fn unescape(&self, s: &str) -> Option<String> {
+        let s = if self.flags.contains(UriParamsFlags::WWW_FORM) {
+            Cow::Owned(s.replace('+', " "))
+        } else {
+            Cow::Borrowed(s)
+        };
+
+        let relaxed = self.flags.contains(UriParamsFlags::PARSE_RELAXED);
+        match ::uri_unescape_string(&s, None) {
+            Some(unescaped) => {
+                // Percent-encoding can produce arbitrary bytes, so don't go via `as_str()`
+                let ptr: *const c_char = unescaped.to_glib_none().0;
+                let bytes = unsafe { CStr::from_ptr(ptr) }.to_bytes();
+                match str::from_utf8(bytes) {
+                    Ok(unescaped) => Some(String::from(unescaped)),
+                    Err(_) if relaxed => Some(String::from_utf8_lossy(bytes).into_owned()),
+                    Err(_) => None,
+                }
+            }
+            None if relaxed => Some(s.into_owned()),
+            None => None,
+        }
+    }
+}
+
+impl<'a> Iterator for UriParamsIter<'a> {
+    type Item = (String, Option<String>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.params.is_empty() {
+                return None;
+            }
+
+            let separators = self.separators;
+            let (param, rest) = match self.params.find(|c: char| separators.contains(c)) {
+                Some(pos) => (&self.params[..pos], &self.params[pos + 1..]),
+                None => (self.params, ""),
+            };
+            self.params = rest;
+
+            if param.is_empty() {
+                continue;
+            }
+
+            let (key, value) = match param.find('=') {
+                Some(pos) => (&param[..pos], Some(&param[pos + 1..])),
+                None => (param, None),
+            };
+
+            let key = match self.unescape(key) {
+                Some(key) => key,
+                None => break,
+            };
+
+            let value = match value {
+                Some(value) => match self.unescape(value) {
+                    Some(value) => Some(value),
+                    None => break,
+                },
+                None => None,
+            };
+
+            return Some((key, value));
+        }
+
+        self.params = "";
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(
+        params: &str,
+        separators: &str,
+        flags: UriParamsFlags,
+    ) -> Vec<(String, Option<String>)> {
+        UriParamsIter::new(params, separators, flags).collect()
+    }
+
+    fn pair(key: &str, value: Option<&str>) -> (String, Option<String>) {
+        (String::from(key), value.map(String::from))
+    }
+
+    #[test]
+    fn test_uri_params() {
+        assert_eq!(
+            collect("a=1&b=%20x&&c", "&", UriParamsFlags::NONE),
+            vec![pair("a", Some("1")), pair("b", Some(" x")), pair("c", None)]
+        );
+
+        let flags = UriParamsFlags::CASE_INSENSITIVE | UriParamsFlags::WWW_FORM;
+        assert_eq!(
+            collect("A=x+y;b=2", "&;", flags),
+            vec![pair("A", Some("x y")), pair("b", Some("2"))]
+        );
+    }
+
+    #[test]
+    fn test_uri_params_invalid() {
+        let params = "a=1&b=%zz&c=3";
+
+        let strict = collect(params, "&", UriParamsFlags::NONE);
+        assert_eq!(strict, vec![pair("a", Some("1"))]);
+
+        let relaxed = collect(params, "&", UriParamsFlags::PARSE_RELAXED);
+        assert_eq!(
+            relaxed,
+            vec![pair("a", Some("1")), pair("b", Some("%zz")), pair("c", Some("3"))]
+        );
+    }
+
+    #[test]
+    fn test_uri_params_invalid_utf8() {
+        let params = "a=1&b=%ff&c=3";
+
+        let strict = collect(params, "&", UriParamsFlags::NONE);
+        assert_eq!(strict, vec![pair("a", Some("1"))]);
+
+        let relaxed = collect(params, "&", UriParamsFlags::PARSE_RELAXED);
+        assert_eq!(
+            relaxed,
+            vec![pair("a", Some("1")), pair("b", Some("\u{fffd}")), pair("c", Some("3"))]
+        );
+    }
+}
@@ -149,6 +149,54 @@ pub fn filename_from_uri(uri: &str) -> Result<(std::path::PathBuf, Option<GStrin
     }
 }
 
+/// Joins `parts` into a filename using the platform's directory separator.
+pub fn build_filename<I, S>(parts: I) -> PathBuf
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let parts = parts.into_iter().collect::<Vec<_>>();
+    let parts = parts.iter().map(AsRef::as_ref).collect::<Vec<&str>>();
+
+    unsafe { from_glib_full(glib_sys::g_build_filenamev(parts.to_glib_none().0)) }
+}
+
+pub fn filename_to_utf8<P: AsRef<OsStr>>(filename: P) -> Result<String, Error> {
+    unsafe {
+        let mut error = ptr::null_mut();
+        let ret = glib_sys::g_filename_to_utf8(
+            filename.as_ref().to_glib_none().0,
+            -1,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut error,
+        );
+        if error.is_null() {
+            Ok(from_glib_full(ret))
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
+pub fn filename_from_utf8(utf8string: &str) -> Result<OsString, Error> {
+    unsafe {
+        let mut error = ptr::null_mut();
+        let ret = glib_sys::g_filename_from_utf8(
+            utf8string.to_glib_none().0,
+            -1,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut error,
+        );
+        if error.is_null() {
+            Ok(from_glib_full(ret))
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
 pub fn find_program_in_path<P: AsRef<Path>>(program: P) -> Option<PathBuf> {
     #[cfg(not(all(windows, target_arch = "x86")))]
     use glib_sys::g_find_program_in_path;
@@ -158,6 +206,7 @@ pub fn find_program_in_path<P: AsRef<Path>>(program: P) -> Option<PathBuf> {
     unsafe { from_glib_full(g_find_program_in_path(program.as_ref().to_glib_none().0)) }
 }
 
+#[must_use]
 pub fn get_home_dir() -> Option<std::path::PathBuf> {
     #[cfg(not(all(windows, target_arch = "x86")))]
     use glib_sys::g_get_home_dir;
@@ -185,6 +234,28 @@ pub fn mkstemp<P: AsRef<std::path::Path>>(tmpl: P) -> i32 {
     unsafe { g_mkstemp(tmpl.as_ref().to_glib_none().0) }
 }
 
+/// Checks if `data` is valid UTF-8 according to GLib's definition.
+///
+/// Different to `std::str::from_utf8()` this also rejects embedded nul bytes, which GLib
+/// text APIs can't handle.
+pub fn utf8_validate(data: &[u8]) -> bool {
+    unsafe {
+        from_glib(glib_sys::g_utf8_validate(
+            data.as_ptr() as *const _,
+            data.len() as isize,
+            ptr::null_mut(),
+        ))
+    }
+}
+
+/// Returns the number of characters in `s` as counted by GLib.
+///
+/// GLib text APIs usually use character offsets instead of byte offsets. Counting stops at
+/// the first embedded nul byte.
+pub fn utf8_char_len(s: &str) -> usize {
+    unsafe { glib_sys::g_utf8_strlen(s.as_ptr() as *const _, s.len() as isize) as usize }
+}
+
 #[cfg(test)]
 mod tests {
     use std::env;
@@ -245,4 +316,85 @@ mod tests {
             unreachable!();
         }
     }
+
+    #[test]
+    fn test_filename_utf8() {
+        use std::ffi::OsString;
+
+        let filename = ::filename_from_utf8("foo/bär.txt").unwrap();
+        assert_eq!(::filename_to_utf8(&filename).unwrap(), "foo/bär.txt");
+        assert_eq!(::filename_to_utf8(OsString::from("foo/bar.txt")).unwrap(), "foo/bar.txt");
+    }
+
+    #[test]
+    fn test_utf8() {
+        assert!(::utf8_validate(b"foo b\xc3\xa4r"));
+        assert!(!::utf8_validate(b"foo\xff"));
+        assert!(!::utf8_validate(b"foo\0bar"));
+        assert!(::utf8_validate(b""));
+
+        assert_eq!(::utf8_char_len("foo bär"), 7);
+        assert_eq!(::utf8_char_len("Тест"), 4);
+        assert_eq!(::utf8_char_len(""), 0);
+        assert_eq!(::utf8_char_len("foo\0bar"), 3);
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(::format_size(4_200_000).unwrap(), "4.2 MB");
+        assert_eq!(::format_size_full(1024, ::FormatSizeFlags::IEC_UNITS).unwrap(), "1.0 KiB");
+        assert_eq!(::format_size_full(1000, ::FormatSizeFlags::BITS).unwrap(), "1.0 kb");
+    }
+
+    #[test]
+    fn test_user_dirs() {
+        for dir in &[
+            ::get_home_dir(),
+            ::get_user_data_dir(),
+            ::get_user_config_dir(),
+            ::get_user_cache_dir(),
+            ::get_user_runtime_dir(),
+        ] {
+            assert!(dir.as_ref().unwrap().is_absolute());
+        }
+    }
+
+    #[test]
+    fn test_build_filename() {
+        use std::path::PathBuf;
+
+        let mut expected = PathBuf::from("foo");
+        expected.push("bar");
+        expected.push("baz.txt");
+        assert_eq!(::build_filename(&["foo", "bar", "baz.txt"]), expected);
+        assert_eq!(::build_filename(vec![String::from("foo")]), PathBuf::from("foo"));
+    }
+
+    #[test]
+    fn test_path_is_absolute() {
+        assert!(::path_is_absolute(::get_current_dir().unwrap()));
+        assert!(!::path_is_absolute("foo/bar"));
+    }
+
+    #[cfg(any(feature = "v2_58", feature = "dox"))]
+    #[test]
+    fn test_canonicalize_filename() {
+        let base = ::get_current_dir().unwrap();
+        let canonical = ::canonicalize_filename("foo/./bar/../baz", &base).unwrap();
+        assert_eq!(canonical, base.join("foo").join("baz"));
+    }
+
+    #[test]
+    fn test_file_contents() {
+        let path = ::get_tmp_dir()
+            .unwrap()
+            .join(format!("glib-file-contents-test-{}", std::process::id()));
+
+        ::file_set_contents(&path, b"first").unwrap();
+        ::file_set_contents(&path, b"second").unwrap();
+        assert_eq!(::file_get_contents(&path).unwrap(), b"second");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(::file_get_contents(&path).is_err());
+    }
 }
@@ -0,0 +1,106 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+use std::fmt;
+use std::str::FromStr;
+use BoolError;
+
+/// A UUID in the canonical `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` form used
+/// by the GLib UUID functions.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Uuid([u8; 16]);
+
+impl Uuid {
+    pub fn from_bytes(bytes: [u8; 16]) -> Uuid {
+        Uuid(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Generates a new random (version 4) UUID.
+    #[cfg(any(feature = "v2_52", feature = "dox"))]
+    pub fn new_random() -> Uuid {
+        ::uuid_string_random()
+            .expect("Failed to generate random UUID")
+            .parse()
+            .expect("Invalid random UUID")
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = BoolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.as_bytes();
+        if s.len() != 36 {
+            return Err(glib_bool_error!("Invalid UUID length"));
+        }
+
+        let mut bytes = [0u8; 16];
+        let mut digits = s
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != 8 && i != 13 && i != 18 && i != 23);
+        for byte in bytes.iter_mut() {
+            let mut value = 0;
+            for _ in 0..2 {
+                let (_, &c) = digits.next().unwrap();
+                let digit = (c as char)
+                    .to_digit(16)
+                    .ok_or_else(|| glib_bool_error!("Invalid UUID character"))?;
+                value = (value << 4) | digit as u8;
+            }
+            *byte = value;
+        }
+
+        if s[8] != b'-' || s[13] != b'-' || s[18] != b'-' || s[23] != b'-' {
+            return Err(glib_bool_error!("Invalid UUID separator"));
+        }
+
+        Ok(Uuid(bytes))
+    }
+}
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if i == 4 || i == 6 || i == 8 || i == 10 {
+                f.write_str("-")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid() {
+        let s = "0badf00d-cafe-4b1d-8bad-d00dfeedface";
+        let uuid = s.parse::<Uuid>().unwrap();
+        assert_eq!(uuid.as_bytes()[..4], [0x0b, 0xad, 0xf0, 0x0d]);
+        assert_eq!(uuid.to_string(), s);
+        assert_eq!("0BADF00D-CAFE-4B1D-8BAD-D00DFEEDFACE".parse::<Uuid>().unwrap(), uuid);
+
+        assert!("0badf00d-cafe-4b1d-8bad-d00dfeedfac".parse::<Uuid>().is_err());
+        assert!("0badf00d-cafe-4b1d-8bad-d00dfeedfacg".parse::<Uuid>().is_err());
+        assert!("0badf00d_cafe-4b1d-8bad-d00dfeedface".parse::<Uuid>().is_err());
+    }
+
+    #[cfg(any(feature = "v2_52", feature = "dox"))]
+    #[test]
+    fn test_uuid_random() {
+        let s = ::uuid_string_random().unwrap();
+        assert!(::uuid_string_is_valid(&s));
+
+        let uuid = Uuid::new_random();
+        assert!(::uuid_string_is_valid(&uuid.to_string()));
+    }
+}
@@ -81,13 +81,16 @@
 
 use libc::{c_char, c_void};
 use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::error;
 use std::ffi::CStr;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem;
 use std::ops::Deref;
+use std::panic;
 use std::ptr;
+use std::sync::Mutex;
 
 use glib_sys;
 use gobject_sys;
@@ -304,12 +307,38 @@ impl Drop for Value {
 
 impl fmt::Debug for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        unsafe {
-            let s: GString =
-                from_glib_full(gobject_sys::g_strdup_value_contents(self.to_glib_none().0));
+        let type_ = self.type_();
+        let mut d = f.debug_struct("Value");
+        d.field("type", &type_.name());
 
-            f.debug_tuple("Value").field(&s).finish()
+        unsafe {
+            let value = self.to_glib_none().0;
+            match type_ {
+                Type::I32 => d.field("value", &gobject_sys::g_value_get_int(value)),
+                Type::F64 => d.field("value", &gobject_sys::g_value_get_double(value)),
+                Type::Bool => d.field("value", &(gobject_sys::g_value_get_boolean(value) != 0)),
+                Type::String => match self.get::<&str>() {
+                    Ok(Some(s)) => d.field("value", &s),
+                    _ => d.field("value", &format_args!("NULL")),
+                },
+                _ if type_.is_a(&Type::BaseObject) => {
+                    let obj = gobject_sys::g_value_get_object(value);
+                    if obj.is_null() {
+                        d.field("value", &format_args!("NULL"))
+                    } else {
+                        let obj_type: Type = from_glib((*(*obj).g_type_instance.g_class).g_type);
+                        d.field("value", &format_args!("{} @ {:?}", obj_type.name(), obj))
+                    }
+                }
+                Type::Invalid => d.field("value", &format_args!("<uninitialized>")),
+                _ => {
+                    let s: GString = from_glib_full(gobject_sys::g_strdup_value_contents(value));
+                    d.field("value", &format_args!("{}", s))
+                }
+            };
         }
+
+        d.finish()
     }
 }
 
@@ -601,6 +630,18 @@ impl Drop for ValueArray {
 pub struct TypedValue<T>(Value, PhantomData<*const T>);
 
 impl<'a, T: FromValueOptional<'a> + SetValue> TypedValue<T> {
+    /// Creates a new `TypedValue` containing `value`.
+    pub fn new(value: T) -> Self {
+        TypedValue(Value::from(&value), PhantomData)
+    }
+
+    /// Tries to convert a `Value` into a `TypedValue`.
+    ///
+    /// Returns `None` if the value does not carry a type corresponding to `T`.
+    pub fn try_from(value: Value) -> Option<Self> {
+        value.downcast().ok()
+    }
+
     /// Returns the value.
     ///
     /// Types that don't support a `None` value always return `Some`. See
@@ -1079,6 +1120,74 @@ numeric!(u64, g_value_get_uint64, g_value_set_uint64);
 numeric!(f32, g_value_get_float, g_value_set_float);
 numeric!(f64, g_value_get_double, g_value_set_double);
 
+type TransformFunc = fn(&Value) -> Value;
+
+static TRANSFORM_FUNCS: once_cell::sync::Lazy<Mutex<HashMap<(usize, usize), TransformFunc>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Registers `func` for transforming `Value`s of type `F` into `Value`s of type `T`.
+///
+/// After this `Value::transform()` and the GLib functions using `g_value_transform()` can
+/// convert between the two types. Registering a function for the same types again replaces
+/// the previous one.
+///
+/// `func` must return a `Value` of type `T`. If it doesn't, or if it panics, a critical is
+/// logged and the destination value is left at the default value of `T`.
+pub fn register_value_transform<F: StaticType, T: StaticType>(func: fn(&Value) -> Value) {
+    unsafe extern "C" fn transform_trampoline<F: StaticType, T: StaticType>(
+        src: *const gobject_sys::GValue,
+        dest: *mut gobject_sys::GValue,
+    ) {
+        // This is called from C, so nothing in here must unwind
+        let key = (F::static_type().to_glib(), T::static_type().to_glib());
+        let func = TRANSFORM_FUNCS
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .get(&key)
+            .cloned();
+        let func = match func {
+            Some(func) => func,
+            None => {
+                ::log_critical("No value transform function registered");
+                return;
+            }
+        };
+
+        let src = &*(src as *const Value);
+        let ret = match panic::catch_unwind(panic::AssertUnwindSafe(|| func(src))) {
+            Ok(ret) => ret,
+            Err(_) => {
+                ::log_critical("Value transform function panicked");
+                return;
+            }
+        };
+
+        if gobject_sys::g_value_type_compatible(ret.type_().to_glib(), (*dest).g_type)
+            == glib_sys::GFALSE
+        {
+            ::log_critical(&format!(
+                "Value transform function returned a value of type {} instead of {}",
+                ret.type_(),
+                T::static_type()
+            ));
+            return;
+        }
+
+        gobject_sys::g_value_copy(ret.to_glib_none().0, dest);
+    }
+
+    let key = (F::static_type().to_glib(), T::static_type().to_glib());
+    if TRANSFORM_FUNCS.lock().unwrap().insert(key, func).is_none() {
+        unsafe {
+            gobject_sys::g_value_register_transform_func(
+                key.0,
+                key.1,
+                Some(transform_trampoline::<F, T>),
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1093,6 +1202,39 @@ mod tests {
         thread::spawn(move || drop(v)).join().unwrap();
     }
 
+    #[test]
+    fn test_debug() {
+        assert_eq!(format!("{:?}", 42.to_value()), "Value { type: \"gint\", value: 42 }");
+        assert_eq!(
+            format!("{:?}", "foo".to_value()),
+            "Value { type: \"gchararray\", value: \"foo\" }"
+        );
+        assert_eq!(
+            format!("{:?}", None::<&str>.to_value()),
+            "Value { type: \"gchararray\", value: NULL }"
+        );
+        assert_eq!(
+            format!("{:?}", 1.5f64.to_value()),
+            "Value { type: \"gdouble\", value: 1.5 }"
+        );
+        assert_eq!(
+            format!("{:?}", true.to_value()),
+            "Value { type: \"gboolean\", value: true }"
+        );
+        assert_eq!(
+            format!("{:?}", 42u64.to_value()),
+            "Value { type: \"guint64\", value: 42 }"
+        );
+        assert_eq!(
+            format!("{:?}", (-7i64).to_value()),
+            "Value { type: \"gint64\", value: -7 }"
+        );
+
+        let obj = ::Object::new(::Object::static_type(), &[]).unwrap();
+        let s = format!("{:?}", obj.to_value());
+        assert!(s.starts_with("Value { type: \"GObject\", value: GObject @ 0x"));
+    }
+
     #[test]
     fn test_strv() {
         let v = vec!["123", "456"].to_value();
@@ -1108,6 +1250,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_typed_value() {
+        let v = TypedValue::new(123i32);
+        assert_eq!(v.get_some(), 123);
+
+        let v = TypedValue::<i32>::try_from(456.to_value()).unwrap();
+        assert_eq!(v.get_some(), 456);
+        assert!(TypedValue::<bool>::try_from(456.to_value()).is_none());
+    }
+
     #[test]
     fn test_get() {
         let v = 123.to_value();
@@ -1147,4 +1299,63 @@ mod tests {
             .expect("Failed to transform to string");
         assert_eq!(v2.get::<&str>(), Ok(Some("123")));
     }
+
+    // Transform functions can't be unregistered, so only register them for types that are
+    // private to the tests below
+    #[derive(Clone, Debug, PartialEq)]
+    struct TransformSource(String);
+
+    impl ::subclass::boxed::BoxedType for TransformSource {
+        const NAME: &'static str = "ValueTestTransformSource";
+
+        crate::glib_boxed_type!();
+    }
+
+    crate::glib_boxed_derive_traits!(TransformSource);
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct TransformMismatchSource(String);
+
+    impl ::subclass::boxed::BoxedType for TransformMismatchSource {
+        const NAME: &'static str = "ValueTestTransformMismatchSource";
+
+        crate::glib_boxed_type!();
+    }
+
+    crate::glib_boxed_derive_traits!(TransformMismatchSource);
+
+    #[test]
+    fn test_register_value_transform() {
+        assert!(!Value::type_transformable(
+            TransformSource::static_type(),
+            String::static_type()
+        ));
+
+        register_value_transform::<TransformSource, String>(|value| {
+            let source = value.get_some::<&TransformSource>().unwrap();
+            source.0.to_value()
+        });
+        assert!(Value::type_transformable(
+            TransformSource::static_type(),
+            String::static_type()
+        ));
+
+        let v = TransformSource(String::from("source")).to_value();
+        let v2 = v
+            .transform::<String>()
+            .expect("Failed to transform to string");
+        assert_eq!(v2.get::<&str>(), Ok(Some("source")));
+    }
+
+    #[test]
+    fn test_register_value_transform_wrong_type() {
+        register_value_transform::<TransformMismatchSource, String>(|_| 1.to_value());
+
+        // The wrong value is not copied into the destination
+        let v = TransformMismatchSource(String::from("source")).to_value();
+        let v2 = v
+            .transform::<String>()
+            .expect("Failed to transform to string");
+        assert_eq!(v2.get::<&str>(), Ok(None));
+    }
 }
@@ -2,6 +2,8 @@
 // See the COPYRIGHT file at the top-level directory of this distribution.
 // Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
 
+#![allow(deprecated)]
+
 use glib_sys;
 use gobject_sys;
 use std::cmp::Ordering;
@@ -11,6 +13,11 @@ use translate::*;
 use Value;
 
 glib_wrapper! {
+    /// A boxed array of `Value`s.
+    ///
+    /// Only needed for APIs that still use `GValueArray`, new code should use
+    /// `Vec<Value>` or `&[Value]` instead.
+    #[deprecated(note = "GValueArray is deprecated since GLib 2.32, use Vec<Value> instead")]
     #[derive(Debug)]
     pub struct ValueArray(Boxed<gobject_sys::GValueArray>);
 
@@ -33,6 +40,20 @@ impl ValueArray {
         }
     }
 
+    /// Returns the number of values in the array.
+    pub fn len(&self) -> u32 {
+        unsafe { (*self.to_glib_none().0).n_values }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the value at `index_`, or `None` if it is out of bounds.
+    pub fn get(&self, index_: u32) -> Option<&Value> {
+        (**self).get(index_ as usize)
+    }
+
     pub fn get_nth(&self, index_: u32) -> Option<Value> {
         unsafe {
             from_glib_none(gobject_sys::g_value_array_get_nth(
@@ -62,6 +83,11 @@ impl ValueArray {
         }
     }
 
+    /// Sorts the array with `compare_func`.
+    pub fn sort<F: Fn(&Value, &Value) -> Ordering>(&mut self, compare_func: F) {
+        self.sort_with_data(compare_func)
+    }
+
     pub fn sort_with_data<F: FnMut(&Value, &Value) -> Ordering>(&mut self, compare_func: F) {
         unsafe extern "C" fn compare_func_trampoline(
             a: glib_sys::gconstpointer,
@@ -117,3 +143,55 @@ impl ops::DerefMut for ValueArray {
         }
     }
 }
+
+impl<'a> IntoIterator for &'a ValueArray {
+    type Item = &'a Value;
+    type IntoIter = slice::Iter<'a, Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut ValueArray {
+    type Item = &'a mut Value;
+    type IntoIter = slice::IterMut<'a, Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ToValue;
+
+    #[test]
+    fn test_value_array() {
+        let mut array = ValueArray::new(2);
+        assert!(array.is_empty());
+
+        array.append(&2.to_value());
+        array.append(&3.to_value());
+        array.prepend(&1.to_value());
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.get(0).unwrap().get_some::<i32>(), Ok(1));
+        assert!(array.get(3).is_none());
+
+        array.sort(|a, b| {
+            let a = a.get_some::<i32>().unwrap();
+            let b = b.get_some::<i32>().unwrap();
+            b.cmp(&a)
+        });
+        let mut values = Vec::new();
+        for value in &array {
+            values.push(value.get_some::<i32>().unwrap());
+        }
+        assert_eq!(values, [3, 2, 1]);
+
+        array.remove(1);
+        assert_eq!(array.len(), 2);
+        assert_eq!(array.get(1).unwrap().get_some::<i32>(), Ok(1));
+    }
+}
@@ -198,13 +198,7 @@ impl PartialOrd for Variant {
                 self.to_glib_none().0 as *const _,
                 other.to_glib_none().0 as *const _,
             );
-            if res < 0 {
-                Some(Ordering::Less)
-            } else if res > 0 {
-                Some(Ordering::Equal)
-            } else {
-                Some(Ordering::Greater)
-            }
+            Some(res.cmp(&0))
         }
     }
 }
@@ -412,6 +406,21 @@ mod tests {
         assert!(v1 != v3);
     }
 
+    #[test]
+    fn test_ord() {
+        let v1 = Variant::from(1);
+        let v2 = Variant::from(2);
+        assert!(v1 < v2);
+        assert!(v2 > v1);
+        assert_eq!(v1.partial_cmp(&Variant::from(1)), Some(Ordering::Equal));
+        assert!(Variant::from("a") < Variant::from("b"));
+
+        // Values of different types are not comparable
+        assert_eq!(v1.partial_cmp(&Variant::from(1u32)), None);
+        assert_eq!(v1.partial_cmp(&Variant::from("1")), None);
+        assert!(v1 != Variant::from(1u32));
+    }
+
     #[test]
     fn test_hash() {
         let v1 = Variant::from("this is a test");
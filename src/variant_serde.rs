@@ -0,0 +1,1151 @@
+// Copyright 2020, The Gtk-rs Project Developers.
+// See the COPYRIGHT file at the top-level directory of this distribution.
+// Licensed under the MIT license, see the LICENSE file or <http://opensource.org/licenses/MIT>
+
+//! `serde` support for `Variant`.
+//!
+//! `Variant` itself implements `Serialize` and `Deserialize`. Human-readable
+//! formats use the GVariant text format with type annotations, other formats
+//! use a `(type string, serialized bytes)` tuple. The serialized bytes are always
+//! in little endian byte order, independent of the byte order of the machine.
+//!
+//! In addition [`to_variant`](fn.to_variant.html) and
+//! [`from_variant`](fn.from_variant.html) convert between any type implementing
+//! the `serde` traits and a `Variant`. The `serde` data model is mapped as
+//! follows:
+//!
+//! | `serde`                          | `GVariant`                             |
+//! |----------------------------------|----------------------------------------|
+//! | `bool`                           | `b`                                    |
+//! | `i8`, `i16`                      | `n`                                    |
+//! | `u8`, `u16`, `i32`, `u32`        | `y`, `q`, `i`, `u`                     |
+//! | `i64`, `u64`                     | `x`, `t`                               |
+//! | `f32`, `f64`                     | `d`                                    |
+//! | `char`, string                   | `s`                                    |
+//! | byte array                       | `ay`                                   |
+//! | option                           | `mv`                                   |
+//! | unit, unit struct                | `()`                                   |
+//! | newtype struct                   | the contained value                    |
+//! | seq                              | `a?` of the element type `?` or `av`   |
+//! | tuple, tuple struct              | tuple of the element types             |
+//! | map                              | `a{?v}` with a basic key type `?`      |
+//! | struct                           | `a{sv}`                                |
+//! | unit variant                     | `s`                                    |
+//! | newtype, tuple or struct variant | `(sv)` of the variant name and content |
+//!
+//! Sequences whose elements all have the same type become arrays of that type,
+//! empty sequences and sequences with elements of different types become `av`.
+//!
+//! When deserializing, values boxed in `v` are unboxed transparently, and arrays,
+//! tuples and dictionaries of any element type are accepted as sequences and maps.
+
+use glib_sys;
+use serde::de::{self, IntoDeserializer};
+use serde::forward_to_deserialize_any;
+use serde::ser::{self, SerializeTuple};
+use std::error;
+use std::fmt;
+use std::ptr;
+use std::slice;
+use translate::*;
+use Bytes;
+use ToVariant;
+use Variant;
+use VariantTy;
+use VariantType;
+
+/// An error converting between a `Variant` and a `serde` data type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// Converts `value` into a `Variant`.
+///
+/// See the [module documentation](index.html) for how the types are mapped.
+pub fn to_variant<T: ?Sized + ser::Serialize>(value: &T) -> Result<Variant, Error> {
+    value.serialize(Serializer)
+}
+
+/// Converts `variant` into a `T`.
+///
+/// See the [module documentation](index.html) for how the types are mapped.
+pub fn from_variant<T: de::DeserializeOwned>(variant: &Variant) -> Result<T, Error> {
+    T::deserialize(Deserializer::new(variant.clone()))
+}
+
+fn static_ty(type_string: &'static str) -> &'static VariantTy {
+    unsafe { VariantTy::from_str_unchecked(type_string) }
+}
+
+fn is_basic_type(ty: &VariantTy) -> bool {
+    let ty = ty.to_str();
+    ty.len() == 1 && "bynqiuxthdsog".contains(ty)
+}
+
+fn new_variant(value: &Variant) -> Variant {
+    unsafe { from_glib_none(glib_sys::g_variant_new_variant(value.to_glib_none().0)) }
+}
+
+fn new_tuple(children: &[Variant]) -> Variant {
+    let children: Vec<*mut glib_sys::GVariant> =
+        children.iter().map(|c| c.to_glib_none().0).collect();
+    unsafe { from_glib_none(glib_sys::g_variant_new_tuple(children.as_ptr(), children.len())) }
+}
+
+fn new_array(child_type: &VariantTy, children: &[Variant]) -> Variant {
+    let children: Vec<*mut glib_sys::GVariant> =
+        children.iter().map(|c| c.to_glib_none().0).collect();
+    unsafe {
+        from_glib_none(glib_sys::g_variant_new_array(
+            child_type.as_ptr(),
+            children.as_ptr(),
+            children.len(),
+        ))
+    }
+}
+
+fn new_dict(entries: &[(Variant, Variant)]) -> Result<Variant, Error> {
+    let key_type = match entries.first() {
+        Some(&(ref key, _)) => key.type_().to_owned(),
+        None => static_ty("s").to_owned(),
+    };
+    if !is_basic_type(&key_type) {
+        return Err(Error(format!("Map key type '{}' is not a basic type", key_type)));
+    }
+
+    let mut children = Vec::with_capacity(entries.len());
+    for &(ref key, ref value) in entries {
+        if key.type_() != &*key_type {
+            return Err(Error(format!(
+                "Map key type '{}' does not match '{}'",
+                key.type_(),
+                key_type
+            )));
+        }
+        children.push(unsafe {
+            from_glib_none(glib_sys::g_variant_new_dict_entry(
+                key.to_glib_none().0,
+                new_variant(value).to_glib_none().0,
+            ))
+        });
+    }
+
+    let entry_type = VariantType::new(&format!("{{{}v}}", key_type)).unwrap();
+    Ok(new_array(&entry_type, &children))
+}
+
+// `Variant`s are serialized in the native byte order, while the serialized data always uses
+// little endian. Swapping is its own inverse, so this converts in both directions.
+fn byteswap_if_big_endian(variant: Variant) -> Variant {
+    if cfg!(target_endian = "big") {
+        unsafe { from_glib_full(glib_sys::g_variant_byteswap(variant.to_glib_none().0)) }
+    } else {
+        variant
+    }
+}
+
+fn new_enum_variant(variant: &'static str, content: &Variant) -> Variant {
+    new_tuple(&[variant.to_variant(), new_variant(content)])
+}
+
+fn parse_variant(text: &str) -> Result<Variant, Error> {
+    unsafe {
+        let start = text.as_ptr() as *const _;
+        let limit = (text.as_ptr() as usize + text.len()) as *const _;
+        let mut error = ptr::null_mut();
+        let ret =
+            glib_sys::g_variant_parse(ptr::null(), start, limit, ptr::null_mut(), &mut error);
+        if error.is_null() {
+            Ok(from_glib_full(ret))
+        } else {
+            let error: ::Error = from_glib_full(error);
+            Err(Error(error.to_string()))
+        }
+    }
+}
+
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Variant;
+    type Error = Error;
+    type SerializeSeq = SerializeVec;
+    type SerializeTuple = SerializeVec;
+    type SerializeTupleStruct = SerializeVec;
+    type SerializeTupleVariant = SerializeVec;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeMap;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Variant, Error> {
+        Ok(i16::from(v).to_variant())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Variant, Error> {
+        Ok(f64::from(v).to_variant())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Variant, Error> {
+        Ok(v.to_string().to_variant())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Variant, Error> {
+        Ok(v.to_variant())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Variant, Error> {
+        unsafe {
+            Ok(from_glib_none(glib_sys::g_variant_new_fixed_array(
+                static_ty("y").as_ptr(),
+                v.as_ptr() as *const _,
+                v.len(),
+                1,
+            )))
+        }
+    }
+
+    fn serialize_none(self) -> Result<Variant, Error> {
+        unsafe {
+            Ok(from_glib_none(glib_sys::g_variant_new_maybe(
+                static_ty("v").as_ptr(),
+                ptr::null_mut(),
+            )))
+        }
+    }
+
+    fn serialize_some<T: ?Sized + ser::Serialize>(self, value: &T) -> Result<Variant, Error> {
+        let value = new_variant(&value.serialize(Serializer)?);
+        unsafe {
+            Ok(from_glib_none(glib_sys::g_variant_new_maybe(
+                ptr::null(),
+                value.to_glib_none().0,
+            )))
+        }
+    }
+
+    fn serialize_unit(self) -> Result<Variant, Error> {
+        Ok(new_tuple(&[]))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Variant, Error> {
+        Ok(new_tuple(&[]))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Variant, Error> {
+        Ok(variant.to_variant())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + ser::Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Variant, Error> {
+        value.serialize(Serializer)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + ser::Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Variant, Error> {
+        Ok(new_enum_variant(variant, &value.serialize(Serializer)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeVec, Error> {
+        Ok(SerializeVec::new(None, len.unwrap_or(0)))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeVec, Error> {
+        Ok(SerializeVec::new(None, len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeVec, Error> {
+        Ok(SerializeVec::new(None, len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVec, Error> {
+        Ok(SerializeVec::new(Some(variant), len))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap::new(None, len.unwrap_or(0)))
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap::new(None, len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap::new(Some(variant), len))
+    }
+}
+
+struct SerializeVec {
+    variant: Option<&'static str>,
+    children: Vec<Variant>,
+}
+
+impl SerializeVec {
+    fn new(variant: Option<&'static str>, len: usize) -> Self {
+        SerializeVec {
+            variant,
+            children: Vec::with_capacity(len),
+        }
+    }
+
+    fn push<T: ?Sized + ser::Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.children.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end_tuple(self) -> Variant {
+        let tuple = new_tuple(&self.children);
+        match self.variant {
+            Some(variant) => new_enum_variant(variant, &tuple),
+            None => tuple,
+        }
+    }
+}
+
+impl ser::SerializeSeq for SerializeVec {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + ser::Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        // Use a typed array if possible, otherwise box all elements
+        if let Some(first) = self.children.first() {
+            let child_type = first.type_();
+            if self.children.iter().all(|c| c.type_() == child_type) {
+                return Ok(new_array(child_type, &self.children));
+            }
+        }
+
+        let children: Vec<Variant> = self.children.iter().map(new_variant).collect();
+        Ok(new_array(static_ty("v"), &children))
+    }
+}
+
+impl ser::SerializeTuple for SerializeVec {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + ser::Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        Ok(self.end_tuple())
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeVec {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + ser::Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        Ok(self.end_tuple())
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeVec {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + ser::Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        Ok(self.end_tuple())
+    }
+}
+
+struct SerializeMap {
+    variant: Option<&'static str>,
+    entries: Vec<(Variant, Variant)>,
+    next_key: Option<Variant>,
+}
+
+impl SerializeMap {
+    fn new(variant: Option<&'static str>, len: usize) -> Self {
+        SerializeMap {
+            variant,
+            entries: Vec::with_capacity(len),
+            next_key: None,
+        }
+    }
+
+    fn insert<T: ?Sized + ser::Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.entries.push((key.to_variant(), value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn end_dict(self) -> Result<Variant, Error> {
+        let dict = new_dict(&self.entries)?;
+        match self.variant {
+            Some(variant) => Ok(new_enum_variant(variant, &dict)),
+            None => Ok(dict),
+        }
+    }
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + ser::Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        self.next_key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + ser::Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .next_key
+            .take()
+            .ok_or_else(|| Error(String::from("Map value serialized before its key")))?;
+        self.entries.push((key, value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        self.end_dict()
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + ser::Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        self.end_dict()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeMap {
+    type Ok = Variant;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + ser::Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Variant, Error> {
+        self.end_dict()
+    }
+}
+
+struct Deserializer(Variant);
+
+impl Deserializer {
+    /// Creates a deserializer for `variant`, unboxing any `v` values.
+    fn new(mut variant: Variant) -> Self {
+        while variant.type_().to_str() == "v" {
+            variant = unsafe {
+                from_glib_full(glib_sys::g_variant_get_variant(variant.to_glib_none().0))
+            };
+        }
+        Deserializer(variant)
+    }
+
+    fn n_children(&self) -> usize {
+        unsafe { glib_sys::g_variant_n_children(self.0.to_glib_none().0) }
+    }
+
+    fn child(&self, index: usize) -> Variant {
+        unsafe {
+            from_glib_full(glib_sys::g_variant_get_child_value(
+                self.0.to_glib_none().0,
+                index,
+            ))
+        }
+    }
+
+    fn byte_array(&self) -> Option<&[u8]> {
+        if self.0.type_().to_str() != "ay" {
+            return None;
+        }
+
+        unsafe {
+            let mut len = 0;
+            let data = glib_sys::g_variant_get_fixed_array(self.0.to_glib_none().0, &mut len, 1);
+            if len == 0 {
+                Some(&[])
+            } else {
+                Some(slice::from_raw_parts(data as *const u8, len))
+            }
+        }
+    }
+
+    fn invalid_type<E: de::Expected>(&self, exp: &E) -> Error {
+        de::Error::invalid_type(de::Unexpected::Other(self.0.type_().to_str()), exp)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = Error;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        unsafe {
+            let ptr = self.0.to_glib_none().0;
+            match self.0.type_().to_str().as_bytes()[0] {
+                b'b' => visitor.visit_bool(from_glib(glib_sys::g_variant_get_boolean(ptr))),
+                b'y' => visitor.visit_u8(glib_sys::g_variant_get_byte(ptr)),
+                b'n' => visitor.visit_i16(glib_sys::g_variant_get_int16(ptr)),
+                b'q' => visitor.visit_u16(glib_sys::g_variant_get_uint16(ptr)),
+                b'i' => visitor.visit_i32(glib_sys::g_variant_get_int32(ptr)),
+                b'h' => visitor.visit_i32(glib_sys::g_variant_get_handle(ptr)),
+                b'u' => visitor.visit_u32(glib_sys::g_variant_get_uint32(ptr)),
+                b'x' => visitor.visit_i64(glib_sys::g_variant_get_int64(ptr)),
+                b't' => visitor.visit_u64(glib_sys::g_variant_get_uint64(ptr)),
+                b'd' => visitor.visit_f64(glib_sys::g_variant_get_double(ptr)),
+                b's' | b'o' | b'g' => visitor.visit_str(self.0.get_str().unwrap()),
+                b'm' => {
+                    let child = glib_sys::g_variant_get_maybe(ptr);
+                    if child.is_null() {
+                        visitor.visit_none()
+                    } else {
+                        visitor.visit_some(Deserializer::new(from_glib_full(child)))
+                    }
+                }
+                b'a' if self.0.type_().to_str().starts_with("a{") => {
+                    visitor.visit_map(MapAccess::new(self))
+                }
+                b'(' if self.n_children() == 0 => visitor.visit_unit(),
+                b'a' | b'(' | b'{' => visitor.visit_seq(SeqAccess::new(self)),
+                _ => Err(self.invalid_type(&visitor)),
+            }
+        }
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.byte_array() {
+            Some(bytes) => visitor.visit_bytes(bytes),
+            None => de::Deserializer::deserialize_any(self, visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.0.type_().to_str().starts_with('m') {
+            de::Deserializer::deserialize_any(self, visitor)
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.0.type_().to_str() == "()" {
+            visitor.visit_unit()
+        } else {
+            Err(self.invalid_type(&visitor))
+        }
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0.type_().to_str().as_bytes()[0] {
+            b'a' | b'(' | b'{' => visitor.visit_seq(SeqAccess::new(self)),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0.type_().to_str() {
+            "s" => visitor.visit_enum(EnumAccess {
+                variant: self.0.get_str().unwrap().to_owned(),
+                content: None,
+            }),
+            "(sv)" => visitor.visit_enum(EnumAccess {
+                variant: self.child(0).get_str().unwrap().to_owned(),
+                content: Some(Deserializer::new(self.child(1))),
+            }),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        map struct identifier ignored_any
+    }
+}
+
+struct SeqAccess {
+    de: Deserializer,
+    index: usize,
+    len: usize,
+}
+
+impl SeqAccess {
+    fn new(de: Deserializer) -> Self {
+        let len = de.n_children();
+        SeqAccess { de, index: 0, len }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.index == self.len {
+            return Ok(None);
+        }
+
+        let child = self.de.child(self.index);
+        self.index += 1;
+        seed.deserialize(Deserializer::new(child)).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.index)
+    }
+}
+
+struct MapAccess {
+    entries: SeqAccess,
+    value: Option<Variant>,
+}
+
+impl MapAccess {
+    fn new(de: Deserializer) -> Self {
+        MapAccess {
+            entries: SeqAccess::new(de),
+            value: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if self.entries.index == self.entries.len {
+            return Ok(None);
+        }
+
+        let entry = Deserializer(self.entries.de.child(self.entries.index));
+        self.entries.index += 1;
+        self.value = Some(entry.child(1));
+        seed.deserialize(Deserializer::new(entry.child(0))).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error(String::from("Map value deserialized before its key")))?;
+        seed.deserialize(Deserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len - self.entries.index)
+    }
+}
+
+struct EnumAccess {
+    variant: String,
+    content: Option<Deserializer>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = Error;
+    type Variant = VariantAccess;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantAccess), Error> {
+        let variant: de::value::StringDeserializer<Error> = self.variant.into_deserializer();
+        Ok((seed.deserialize(variant)?, VariantAccess(self.content)))
+    }
+}
+
+struct VariantAccess(Option<Deserializer>);
+
+impl VariantAccess {
+    fn content(self) -> Result<Deserializer, Error> {
+        self.0
+            .ok_or_else(|| de::Error::invalid_type(de::Unexpected::UnitVariant, &"variant content"))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.0 {
+            None => Ok(()),
+            Some(content) => de::Deserialize::deserialize(content),
+        }
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self.content()?)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_tuple(self.content()?, len, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self.content()?, visitor)
+    }
+}
+
+struct SerializeBytes<'a>(&'a [u8]);
+
+impl<'a> ser::Serialize for SerializeBytes<'a> {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+struct DeserializeBytes(Vec<u8>);
+
+impl<'de> de::Deserialize<'de> for DeserializeBytes {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BytesVisitor;
+
+        impl<'de> de::Visitor<'de> for BytesVisitor {
+            type Value = DeserializeBytes;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a byte array")
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<DeserializeBytes, E> {
+                Ok(DeserializeBytes(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<DeserializeBytes, E> {
+                Ok(DeserializeBytes(v))
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<DeserializeBytes, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(DeserializeBytes(bytes))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+impl ser::Serialize for Variant {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let text: ::GString = unsafe {
+                from_glib_full(glib_sys::g_variant_print(
+                    self.to_glib_none().0,
+                    true.to_glib(),
+                ))
+            };
+            serializer.serialize_str(&text)
+        } else {
+            let variant = byteswap_if_big_endian(self.clone());
+            let data: Bytes = unsafe {
+                from_glib_full(glib_sys::g_variant_get_data_as_bytes(
+                    variant.to_glib_none().0,
+                ))
+            };
+            let mut tuple = serializer.serialize_tuple(2)?;
+            tuple.serialize_element(self.type_().to_str())?;
+            tuple.serialize_element(&SerializeBytes(&data))?;
+            tuple.end()
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Variant {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TextVisitor;
+
+        impl<'de> de::Visitor<'de> for TextVisitor {
+            type Value = Variant;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a GVariant in text format")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Variant, E> {
+                parse_variant(v).map_err(E::custom)
+            }
+        }
+
+        struct SerializedVisitor;
+
+        impl<'de> de::Visitor<'de> for SerializedVisitor {
+            type Value = Variant;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a tuple of a GVariant type string and serialized data")
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Variant, A::Error> {
+                let type_string: String = seq
+                    .next_element()?
+                    .ok_or_else(|| <A::Error as de::Error>::invalid_length(0, &self))?;
+                let data: DeserializeBytes = seq
+                    .next_element()?
+                    .ok_or_else(|| <A::Error as de::Error>::invalid_length(1, &self))?;
+
+                let type_ = VariantType::new(&type_string).map_err(|_| {
+                    <A::Error as de::Error>::invalid_value(
+                        de::Unexpected::Str(&type_string),
+                        &"a type string",
+                    )
+                })?;
+                let data = Bytes::from_owned(data.0);
+                let variant = unsafe {
+                    from_glib_none(glib_sys::g_variant_new_from_bytes(
+                        type_.as_ptr(),
+                        data.to_glib_none().0,
+                        false.to_glib(),
+                    ))
+                };
+                Ok(byteswap_if_big_endian(variant))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(TextVisitor)
+        } else {
+            deserializer.deserialize_tuple(2, SerializedVisitor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Serialize};
+    use std::collections::{BTreeMap, HashMap};
+    use std::fmt::Debug;
+
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T, type_: &str) {
+        let variant = to_variant(&value).unwrap();
+        assert_eq!(variant.type_().to_str(), type_);
+        assert_eq!(from_variant::<T>(&variant).unwrap(), value);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct ByteBuf(Vec<u8>);
+
+    impl Serialize for ByteBuf {
+        fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ByteBuf {
+        fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            DeserializeBytes::deserialize(deserializer).map(|bytes| ByteBuf(bytes.0))
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Unit;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Newtype(i32);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Tuple(i32, String);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Struct {
+        a: u8,
+        b: Option<String>,
+        c: Vec<Struct>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Enum {
+        Unit,
+        Newtype(u64),
+        Tuple(bool, f64),
+        Struct { a: char },
+    }
+
+    #[test]
+    fn test_primitives() {
+        round_trip(true, "b");
+        round_trip(-8i8, "n");
+        round_trip(-16i16, "n");
+        round_trip(-32i32, "i");
+        round_trip(-64i64, "x");
+        round_trip(8u8, "y");
+        round_trip(16u16, "q");
+        round_trip(32u32, "u");
+        round_trip(64u64, "t");
+        round_trip(0.5f32, "d");
+        round_trip(0.25f64, "d");
+        round_trip('x', "s");
+        round_trip(String::from("foo"), "s");
+        round_trip(ByteBuf(vec![1, 2, 3]), "ay");
+        round_trip(ByteBuf(vec![]), "ay");
+    }
+
+    #[test]
+    fn test_options() {
+        round_trip(None::<i32>, "mv");
+        round_trip(Some(1i32), "mv");
+        round_trip(Some(None::<i32>), "mv");
+        round_trip(Some(Some(1i32)), "mv");
+    }
+
+    #[test]
+    fn test_structs() {
+        round_trip((), "()");
+        round_trip(Unit, "()");
+        round_trip(Newtype(1), "i");
+        round_trip(Tuple(1, String::from("foo")), "(is)");
+        round_trip(
+            Struct {
+                a: 1,
+                b: None,
+                c: vec![Struct {
+                    a: 2,
+                    b: Some(String::from("foo")),
+                    c: vec![],
+                }],
+            },
+            "a{sv}",
+        );
+    }
+
+    #[test]
+    fn test_enums() {
+        round_trip(Enum::Unit, "s");
+        round_trip(Enum::Newtype(1), "(sv)");
+        round_trip(Enum::Tuple(true, 0.5), "(sv)");
+        round_trip(Enum::Struct { a: 'a' }, "(sv)");
+    }
+
+    #[test]
+    fn test_containers() {
+        round_trip(vec![1i32, 2, 3], "ai");
+        round_trip(vec![String::from("foo")], "as");
+        round_trip(vec![vec![1u64], vec![2, 3]], "aat");
+        round_trip(Vec::<String>::new(), "av");
+        round_trip(vec![Enum::Unit, Enum::Newtype(1)], "av");
+        round_trip((1u8, String::from("foo"), vec![true]), "(ysab)");
+
+        let mut map = HashMap::new();
+        map.insert(String::from("a"), 1i32);
+        map.insert(String::from("b"), 2i32);
+        round_trip(map, "a{sv}");
+        round_trip(HashMap::<String, i32>::new(), "a{sv}");
+
+        let mut map = BTreeMap::new();
+        map.insert(1u32, vec![Enum::Unit]);
+        round_trip(map, "a{uv}");
+
+        let mut map = BTreeMap::new();
+        map.insert((1, 2), 3);
+        assert!(to_variant(&map).is_err());
+    }
+
+    #[test]
+    fn test_from_native_variant() {
+        let variant = parse_variant("(int32 1, 'foo', [uint32 1, 2])").unwrap();
+        let value: (i32, String, Vec<u32>) = from_variant(&variant).unwrap();
+        assert_eq!(value, (1, String::from("foo"), vec![1, 2]));
+
+        let variant = parse_variant("{'a': <1>, 'b': <2>}").unwrap();
+        let value: BTreeMap<String, i32> = from_variant(&variant).unwrap();
+        assert_eq!(value.len(), 2);
+        assert_eq!(value["b"], 2);
+
+        assert!(from_variant::<String>(&1.to_variant()).is_err());
+    }
+
+    #[test]
+    fn test_variant_serde() {
+        let variant = parse_variant("(uint16 1, 'foo', @as [])").unwrap();
+
+        let value = to_variant(&variant).unwrap();
+        assert_eq!(value.type_().to_str(), "(say)");
+        assert_eq!(from_variant::<Variant>(&value).unwrap(), variant);
+
+        // The serialized data is little endian on all machines
+        let value = to_variant(&0x0102u16.to_variant()).unwrap();
+        let (type_string, data): (String, ByteBuf) = from_variant(&value).unwrap();
+        assert_eq!(type_string, "q");
+        assert_eq!(data, ByteBuf(vec![0x02, 0x01]));
+        assert_eq!(from_variant::<Variant>(&value).unwrap(), 0x0102u16.to_variant());
+
+        let text: de::value::StrDeserializer<Error> =
+            "(uint16 1, 'foo', @as [])".into_deserializer();
+        assert_eq!(Variant::deserialize(text).unwrap(), variant);
+
+        let text: de::value::StrDeserializer<Error> = "(1,".into_deserializer();
+        assert!(Variant::deserialize(text).is_err());
+    }
+}